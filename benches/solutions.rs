@@ -0,0 +1,83 @@
+//! Every part of every registered day, on generated inputs at growing scales
+//! and on the real input when there is one. Throughput is reported in input
+//! bytes, so a part that slows down as its input grows shows it.
+//!
+//! Parsing is timed in its own group per day, and each part's group times
+//! only the solving.
+
+use std::{hint::black_box, time::Duration};
+
+use aoc_2025::{
+    SOLUTIONS, Solution, r#gen, input_path, load_input,
+    solution::{Params, Timed},
+};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+/// Multiples of the generator's default size, which is roughly the real
+/// input's, up to the most the day's solutions get through in time.
+const SCALES: [usize; 3] = [1, 4, 16];
+
+fn bench_solution(c: &mut Criterion, solution: &dyn Solution) {
+    let day = solution.day();
+    let default_size = r#gen::default_size(day).expect("every day has a generator");
+    let max_size = r#gen::max_size(day).expect("checked above");
+    let mut inputs = SCALES
+        .iter()
+        .filter(|&&scale| default_size * scale <= max_size)
+        .map(|scale| {
+            let input = r#gen::generate(day, 0, default_size * scale).expect("checked above");
+            (format!("{scale}x"), input.into_bytes())
+        })
+        .collect::<Vec<_>>();
+    match load_input(day) {
+        Ok(input) => inputs.push(("input".to_string(), input.into_owned())),
+        Err(e) => eprintln!(
+            "Skipping the day {day:02} input: {}: {e}",
+            input_path(day).display()
+        ),
+    }
+
+    // Every part parses the same way, so the first one stands for them all.
+    let parse: fn(&Timed) -> Duration = |timed| timed.parse;
+    let solve: fn(&Timed) -> Duration = |timed| timed.solve;
+    let groups = solution
+        .parts()
+        .first()
+        .map(|&part| ("parse".to_string(), part, parse));
+    let groups = groups.into_iter().chain(
+        solution
+            .parts()
+            .iter()
+            .map(|&part| (format!("part-{part}"), part, solve)),
+    );
+    for (name, part, measured) in groups {
+        let mut group = c.benchmark_group(format!("day-{day:02}-{name}"));
+        // The largest inputs take seconds per run for the slower days.
+        group.sample_size(10);
+        for (name, input) in &inputs {
+            group.throughput(Throughput::Bytes(input.len() as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(name), input, |b, input| {
+                b.iter_custom(|iters| {
+                    (0..iters)
+                        .map(|_| {
+                            let timed = solution
+                                .solve_timed(black_box(part), black_box(input), &Params::new())
+                                .expect("the solution declares this part");
+                            measured(&black_box(timed))
+                        })
+                        .sum::<Duration>()
+                });
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_solutions(c: &mut Criterion) {
+    for solution in SOLUTIONS {
+        bench_solution(c, *solution);
+    }
+}
+
+criterion_group!(benches, bench_solutions);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+//! Records the commit the crate is built from, so that the answer cache can
+//! tell builds apart.
+//!
+//! Without `rerun-if-changed` lines this runs again whenever a file in the
+//! package changes, which keeps the `-dirty` suffix up to date.
+
+use std::process::Command;
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn main() {
+    let commit = match git(&["rev-parse", "--short=12", "HEAD"]) {
+        Some(hash)
+            if git(&["status", "--porcelain", "--untracked-files=no"]).as_deref() == Some("") =>
+        {
+            hash
+        }
+        Some(hash) => format!("{hash}-dirty"),
+        None => String::new(),
+    };
+    println!("cargo:rustc-env=AOC_GIT_COMMIT={commit}");
+}
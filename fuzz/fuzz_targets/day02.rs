@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_2025::day02;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let _ = day02::try_solve(input, false);
+    let _ = day02::try_solve(input, true);
+});
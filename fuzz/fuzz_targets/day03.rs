@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_2025::day03;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let _ = day03::try_part_1(input);
+    let _ = day03::try_part_2(input);
+});
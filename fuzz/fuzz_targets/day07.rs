@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_2025::day07;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let _ = day07::try_part_1(input);
+    let _ = day07::try_part_2(input);
+});
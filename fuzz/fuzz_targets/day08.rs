@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_2025::day08;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let _ = day08::try_part_1(input, 1000);
+    let _ = day08::try_part_2(input);
+});
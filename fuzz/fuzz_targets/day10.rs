@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_2025::day10;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let _ = day10::try_part_1(input);
+    let _ = day10::try_part_2(input);
+});
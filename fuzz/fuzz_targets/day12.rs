@@ -0,0 +1,8 @@
+#![no_main]
+
+use aoc_2025::day12;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let _ = day12::try_part_1(input);
+});
@@ -0,0 +1,212 @@
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{Answer, Part, runner::DayRun};
+
+/// Expected answers, read from a TOML file such as
+///
+/// ```toml
+/// [day01]
+/// part1 = 1234
+/// part2 = "5678"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    expected: BTreeMap<(u8, Part), String>,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Invalid { key: String, reason: &'static str },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io(e) => e.fmt(f),
+            AnswersError::Toml(e) => e.fmt(f),
+            AnswersError::Invalid { key, reason } => write!(f, "`{key}`: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+/// Default location of the expected answers, next to `inputs/`.
+pub fn answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, AnswersError> {
+        let table: BTreeMap<String, BTreeMap<String, toml::Value>> =
+            toml::from_str(s).map_err(AnswersError::Toml)?;
+
+        let mut expected = BTreeMap::new();
+        for (day_key, parts) in table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| AnswersError::Invalid {
+                    key: day_key.clone(),
+                    reason: "expected a table named `dayNN`",
+                })?;
+            for (part_key, value) in parts {
+                let key = format!("{day_key}.{part_key}");
+                let part = part_key
+                    .strip_prefix("part")
+                    .and_then(|part| part.parse().ok())
+                    .and_then(Part::from_number)
+                    .ok_or_else(|| AnswersError::Invalid {
+                        key: key.clone(),
+                        reason: "expected `part1` or `part2`",
+                    })?;
+                let answer = match value {
+                    toml::Value::Integer(n) => n.to_string(),
+                    toml::Value::String(s) => s,
+                    _ => {
+                        return Err(AnswersError::Invalid {
+                            key,
+                            reason: "expected an integer or a string",
+                        });
+                    }
+                };
+                expected.insert((day, part), answer);
+            }
+        }
+        Ok(Answers { expected })
+    }
+
+    /// Reads `path`, treating a missing file as having no expected answers.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        match Answers::read(path) {
+            Err(AnswersError::Io(e)) if e.kind() == io::ErrorKind::NotFound => {
+                Ok(Answers::default())
+            }
+            answers => answers,
+        }
+    }
+
+    /// Reads `path`, which must exist.
+    pub fn read(path: &Path) -> Result<Self, AnswersError> {
+        Answers::parse(&std::fs::read_to_string(path).map_err(AnswersError::Io)?)
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.expected.get(&(day, part)).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// No expected answer is recorded for this part.
+    Missing,
+    NoInput,
+    ParseError,
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    pub status: Status,
+}
+
+impl Check {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, Status::Fail { .. } | Status::ParseError)
+    }
+}
+
+pub fn check(runs: &[DayRun], answers: &Answers) -> Vec<Check> {
+    let mut checks = Vec::new();
+    for run in runs {
+        let day = run.solution.day();
+        let Ok(parts) = &run.parts else {
+            checks.extend(run.solution.parts().iter().map(|&part| Check {
+                day,
+                part,
+                answer: None,
+                status: Status::NoInput,
+            }));
+            continue;
+        };
+
+        checks.extend(parts.iter().map(|part| {
+            let answer = part.answer.as_ref().ok().copied();
+            let status = match (answer, answers.get(day, part.part)) {
+                (None, _) => Status::ParseError,
+                (Some(_), None) => Status::Missing,
+                (Some(answer), Some(expected)) if answer.to_string() == expected => Status::Pass,
+                (Some(_), Some(expected)) => Status::Fail {
+                    expected: expected.to_string(),
+                },
+            };
+            Check {
+                day,
+                part: part.part,
+                answer,
+                status,
+            }
+        }));
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{day01::Day01, runner::run_input};
+
+    const EXAMPLE: &[u8] = b"L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            Answers::parse("[day01]\npart1 = 3\npart2 = \"6\"\n\n[day12]\npart1 = 2\n").unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("3"));
+        assert_eq!(answers.get(1, Part::Two), Some("6"));
+        assert_eq!(answers.get(12, Part::One), Some("2"));
+        assert_eq!(answers.get(12, Part::Two), None);
+
+        assert!(Answers::parse("[day01]\npart3 = 1\n").is_err());
+        assert!(Answers::parse("[first]\npart1 = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.toml");
+        assert_eq!(Answers::load(&path).unwrap().get(1, Part::One), None);
+        assert!(matches!(Answers::read(&path), Err(AnswersError::Io(_))));
+    }
+
+    #[test]
+    fn test_check() {
+        let runs = [run_input(&Day01, EXAMPLE, Duration::ZERO, None)];
+        let answers = Answers::parse("[day01]\npart1 = 4\n").unwrap();
+
+        let statuses: Vec<Status> = check(&runs, &answers)
+            .into_iter()
+            .map(|check| check.status)
+            .collect();
+        assert_eq!(statuses, [
+            Status::Fail {
+                expected: "4".to_string()
+            },
+            Status::Missing
+        ]);
+    }
+}
@@ -0,0 +1,317 @@
+//! One day run against every input in a directory, such as inputs collected
+//! from several people.
+
+use std::{
+    any::Any,
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    Answer, Part, Solution,
+    runner::{self, PartRun},
+    threads,
+};
+
+/// A part that panicked instead of giving an answer or an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panic {
+    pub part: Part,
+    pub message: String,
+}
+
+pub struct FileRun {
+    pub path: PathBuf,
+    pub parts: io::Result<Vec<Result<PartRun, Panic>>>,
+}
+
+impl FileRun {
+    /// Whether the file could not be read or some part did not produce an
+    /// answer.
+    pub fn is_failure(&self) -> bool {
+        self.parts.as_ref().map_or(true, |parts| {
+            parts
+                .iter()
+                .any(|part| part.as_ref().map_or(true, |run| run.answer.is_err()))
+        })
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string())
+}
+
+/// [`runner::run_part`] for every part of `solution`, catching the panics of
+/// each.
+pub fn run_input(solution: &dyn Solution, input: &[u8]) -> Vec<Result<PartRun, Panic>> {
+    solution
+        .parts()
+        .iter()
+        .filter_map(|&part| {
+            match panic::catch_unwind(AssertUnwindSafe(|| runner::run_part(solution, part, input)))
+            {
+                Ok(run) => run.map(Ok),
+                Err(payload) => Some(Err(Panic {
+                    part,
+                    message: panic_message(&*payload),
+                })),
+            }
+        })
+        .collect()
+}
+
+/// Runs `solution` on every file in `dir`, in path order, spreading the
+/// files over [`threads::map`].
+pub fn run_dir(solution: &dyn Solution, dir: &Path) -> io::Result<Vec<FileRun>> {
+    let mut paths = Vec::new();
+    for entry in dir.read_dir()? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+
+    Ok(threads::map(paths, |path| {
+        let parts = std::fs::read(&path).map(|input| run_input(solution, &input));
+        FileRun { path, parts }
+    }))
+}
+
+/// Flat, machine-readable result of one file and part.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub file: String,
+    pub part: Option<u8>,
+    pub answer: Option<Answer>,
+    #[serde(rename = "type")]
+    pub answer_type: Option<&'static str>,
+    pub parse_ns: Option<u128>,
+    pub elapsed_ns: Option<u128>,
+    pub error: Option<String>,
+    pub panic: Option<String>,
+}
+
+pub fn records(runs: &[FileRun]) -> Vec<Record> {
+    let mut records = Vec::new();
+    for run in runs {
+        let file = run.path.display().to_string();
+        match &run.parts {
+            Ok(parts) => records.extend(parts.iter().map(|part| match part {
+                Ok(run) => Record {
+                    file: file.clone(),
+                    part: Some(run.part.number()),
+                    answer: run.answer.as_ref().ok().copied(),
+                    answer_type: run.answer.as_ref().ok().map(Answer::type_name),
+                    parse_ns: Some(run.parse.as_nanos()),
+                    elapsed_ns: Some(run.elapsed.as_nanos()),
+                    error: run.answer.as_ref().err().map(ToString::to_string),
+                    panic: None,
+                },
+                Err(panic) => Record {
+                    file: file.clone(),
+                    part: Some(panic.part.number()),
+                    answer: None,
+                    answer_type: None,
+                    parse_ns: None,
+                    elapsed_ns: None,
+                    error: None,
+                    panic: Some(panic.message.clone()),
+                },
+            })),
+            Err(e) => records.push(Record {
+                file,
+                part: None,
+                answer: None,
+                answer_type: None,
+                parse_ns: None,
+                elapsed_ns: None,
+                error: Some(format!("unreadable: {e}")),
+                panic: None,
+            }),
+        }
+    }
+    records
+}
+
+pub fn write_json(mut out: impl Write, runs: &[FileRun]) -> io::Result<()> {
+    for record in records(runs) {
+        serde_json::to_writer(&mut out, &record)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+pub fn write_tsv(mut out: impl Write, runs: &[FileRun]) -> io::Result<()> {
+    writeln!(
+        out,
+        "file\tpart\tanswer\ttype\tparse_ns\telapsed_ns\terror\tpanic"
+    )?;
+    for record in records(runs) {
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            record.file,
+            record.part.map(|n| n.to_string()).unwrap_or_default(),
+            record.answer.map(|a| a.to_string()).unwrap_or_default(),
+            record.answer_type.unwrap_or_default(),
+            record.parse_ns.map(|n| n.to_string()).unwrap_or_default(),
+            record.elapsed_ns.map(|n| n.to_string()).unwrap_or_default(),
+            record
+                .error
+                .as_deref()
+                .and_then(|e| e.lines().next())
+                .unwrap_or_default(),
+            record
+                .panic
+                .as_deref()
+                .and_then(|e| e.lines().next())
+                .unwrap_or_default(),
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_table(mut out: impl Write, runs: &[FileRun]) -> io::Result<()> {
+    let width = runs
+        .iter()
+        .map(|run| file_name(&run.path).len())
+        .chain([4])
+        .max()
+        .unwrap();
+    writeln!(
+        out,
+        "{:<width$} {:>4} {:>16} {:>10} {:>10}",
+        "File", "Part", "Answer", "Parse", "Solve"
+    )?;
+
+    for run in runs {
+        let file = file_name(&run.path);
+        let Ok(parts) = &run.parts else {
+            writeln!(
+                out,
+                "{file:<width$} {:>4} {:>16} {:>10} {:>10}",
+                "-", "unreadable", "-", "-"
+            )?;
+            continue;
+        };
+
+        for (i, part) in parts.iter().enumerate() {
+            let (part, answer, parse, solve) = match part {
+                Ok(run) => (
+                    run.part,
+                    run.answer
+                        .as_ref()
+                        .map_or("parse error".to_string(), ToString::to_string),
+                    format!("{:.2?}", run.parse),
+                    format!("{:.2?}", run.solve()),
+                ),
+                Err(panic) => (
+                    panic.part,
+                    "PANIC".to_string(),
+                    "-".to_string(),
+                    "-".to_string(),
+                ),
+            };
+            let file = if i == 0 { file.as_str() } else { "" };
+            writeln!(
+                out,
+                "{file:<width$} {part:>4} {answer:>16} {parse:>10} {solve:>10}"
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        day01::Day01,
+        solution::{Params, Timed},
+    };
+
+    struct Panics;
+
+    impl Solution for Panics {
+        fn day(&self) -> u8 {
+            99
+        }
+
+        fn title(&self) -> &'static str {
+            "Panics"
+        }
+
+        fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+            match part {
+                Part::One => Some(Timed::measure(input, Ok, |input| Ok(input.len()))),
+                Part::Two => panic!("part 2 of {} bytes", input.len()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.txt"),
+            "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("b.txt"), "L68\nX30").unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+
+        let runs = run_dir(&Day01, dir.path()).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(!runs[0].is_failure());
+        assert!(runs[1].is_failure());
+        let answers = runs[0]
+            .parts
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|part| part.as_ref().unwrap().answer.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(answers, [Answer::U32(3), Answer::I32(6)]);
+    }
+
+    #[test]
+    fn test_panics_are_caught() {
+        let parts = run_input(&Panics, b"abc");
+        assert_eq!(
+            parts[0].as_ref().unwrap().answer.clone().unwrap(),
+            Answer::Usize(3)
+        );
+        assert_eq!(
+            parts[1].as_ref().err(),
+            Some(&Panic {
+                part: Part::Two,
+                message: "part 2 of 3 bytes".into()
+            })
+        );
+
+        let runs = [FileRun {
+            path: "inputs/alice.txt".into(),
+            parts: Ok(parts),
+        }];
+        let mut out = Vec::new();
+        write_table(&mut out, &runs).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.lines().nth(1).unwrap().starts_with("alice.txt"));
+        assert!(out.lines().nth(2).unwrap().contains("PANIC"));
+    }
+}
@@ -0,0 +1,233 @@
+//! Quick timings of every part, saved as named baselines under
+//! `benches/baselines/` so a later run can tell what got slower.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ParseError, Solution, runner::input_hash};
+
+/// Where the named baselines are kept, under version control.
+pub fn baselines_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/baselines")
+}
+
+/// Location of the baseline called `name`, or `None` if `name` is not a
+/// plain file name.
+pub fn baseline_path(name: &str) -> Option<PathBuf> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    valid.then(|| baselines_dir().join(format!("{name}.json")))
+}
+
+/// Median time of one part on one input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: u8,
+    pub part: u8,
+    /// [`input_hash`] of the input, as in the JSON report.
+    pub input_hash: String,
+    pub median_ns: u64,
+}
+
+impl Timing {
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub iterations: u32,
+    pub timings: Vec<Timing>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json)
+    }
+}
+
+/// Times every available part of `solution` on `input` over `iterations`
+/// runs after a warm-up run, stopping at the first part that fails.
+pub fn time_parts(
+    solution: &dyn Solution,
+    input: &[u8],
+    iterations: u32,
+) -> Result<Vec<Timing>, ParseError> {
+    let input_hash = format!("{:016x}", input_hash(input));
+    let mut timings = Vec::new();
+    for &part in solution.parts() {
+        let Some(warm_up) = solution.solve(part, input) else {
+            continue;
+        };
+        warm_up?;
+
+        let mut samples = (0..iterations)
+            .map(|_| {
+                let start = Instant::now();
+                let answer = solution.solve(part, input);
+                let elapsed = start.elapsed();
+                std::hint::black_box(answer);
+                elapsed
+            })
+            .collect::<Vec<_>>();
+        samples.sort_unstable();
+        let median = samples.get(samples.len() / 2).copied().unwrap_or_default();
+
+        timings.push(Timing {
+            day: solution.day(),
+            part: part.number(),
+            input_hash: input_hash.clone(),
+            median_ns: median.as_nanos().try_into().unwrap_or(u64::MAX),
+        });
+    }
+    Ok(timings)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    /// The baseline has no timing for this part.
+    New,
+    /// The baseline timed a different input.
+    InputChanged,
+    /// Slower by this many percent, or faster if negative, within the
+    /// threshold.
+    Within(f64),
+    /// Slower by more than the threshold, by this many percent.
+    Regressed(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub timing: Timing,
+    pub before: Option<Duration>,
+    pub change: Change,
+}
+
+/// Compares each timing in `current` with `baseline`, counting a part as
+/// regressed if it got more than `threshold` percent slower.
+pub fn compare(baseline: &Baseline, current: &Baseline, threshold: f64) -> Vec<Comparison> {
+    current
+        .timings
+        .iter()
+        .map(|timing| {
+            let before = baseline
+                .timings
+                .iter()
+                .find(|before| (before.day, before.part) == (timing.day, timing.part));
+            let change = match before {
+                None => Change::New,
+                Some(before) if before.input_hash != timing.input_hash => Change::InputChanged,
+                Some(before) => {
+                    let percent = (timing.median_ns as f64 - before.median_ns as f64)
+                        / (before.median_ns.max(1) as f64)
+                        * 100.0;
+                    if percent > threshold {
+                        Change::Regressed(percent)
+                    } else {
+                        Change::Within(percent)
+                    }
+                }
+            };
+            Comparison {
+                timing: timing.clone(),
+                before: before.map(Timing::median),
+                change,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::Day01;
+
+    fn timing(day: u8, part: u8, input_hash: &str, median_ns: u64) -> Timing {
+        Timing {
+            day,
+            part,
+            input_hash: input_hash.to_string(),
+            median_ns,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = Baseline {
+            iterations: 10,
+            timings: vec![
+                timing(1, 1, "a", 1000),
+                timing(1, 2, "a", 1000),
+                timing(2, 1, "b", 1000),
+            ],
+        };
+        let current = Baseline {
+            iterations: 10,
+            timings: vec![
+                timing(1, 1, "a", 1050),
+                timing(1, 2, "a", 1200),
+                timing(2, 1, "c", 5000),
+                timing(3, 1, "d", 5000),
+            ],
+        };
+        let changes = compare(&baseline, &current, 10.0)
+            .into_iter()
+            .map(|comparison| comparison.change)
+            .collect::<Vec<_>>();
+        assert_eq!(changes, [
+            Change::Within(5.0),
+            Change::Regressed(20.0),
+            Change::InputChanged,
+            Change::New,
+        ]);
+    }
+
+    #[test]
+    fn test_time_parts() {
+        let timings = time_parts(&Day01, b"L68\nL30", 3).unwrap();
+        assert_eq!(timings.len(), 2);
+        assert_eq!((timings[1].day, timings[1].part), (1, 2));
+        assert!(time_parts(&Day01, b"X68", 3).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baselines/main.json");
+        let baseline = Baseline {
+            iterations: 3,
+            timings,
+        };
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+    }
+
+    #[test]
+    fn test_baseline_path() {
+        assert!(
+            baseline_path("main")
+                .unwrap()
+                .ends_with("benches/baselines/main.json")
+        );
+        assert!(baseline_path("v1.2_fast-path").is_some());
+        for name in ["", "../main", "a/b", ".hidden"] {
+            assert_eq!(baseline_path(name), None, "{name}");
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Answers already computed, so that running a slow day again on the same
+//! input is instant.
+//!
+//! The cache lives in `~/.cache/aoc-2025/answers.json` and is keyed by day,
+//! part, [`input_hash`](crate::runner::input_hash) and the build that
+//! computed the answer. Builds with uncommitted changes or built outside git
+//! neither read nor write it, since their answers cannot be told apart. The
+//! answers of the [`MAX_VERSIONS`] most recently used builds are kept, so
+//! switching between branches keeps both caches.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Answer, Part, runner::DayRun};
+
+/// The build's crate version and commit, or `None` if it has uncommitted
+/// changes or its commit is unknown.
+pub fn version() -> Option<String> {
+    let version = env!("CARGO_PKG_VERSION");
+    match env!("AOC_GIT_COMMIT") {
+        "" => None,
+        commit if commit.ends_with("-dirty") => None,
+        commit => Some(format!("{version}+{commit}")),
+    }
+}
+
+/// `$XDG_CACHE_HOME/aoc-2025/answers.json`, or under `~/.cache` if that is
+/// unset.
+pub fn cache_path() -> Option<PathBuf> {
+    let dir = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))?;
+    Some(dir.join("aoc-2025/answers.json"))
+}
+
+/// How many builds' answers the cache keeps.
+pub const MAX_VERSIONS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Entry {
+    day: u8,
+    part: u8,
+    input_hash: String,
+    version: String,
+    #[serde(rename = "type")]
+    answer_type: String,
+    answer: String,
+}
+
+#[derive(Debug)]
+pub struct Cache {
+    path: PathBuf,
+    version: String,
+    entries: Vec<Entry>,
+}
+
+fn hash_key(input_hash: u64) -> String {
+    format!("{input_hash:016x}")
+}
+
+impl Cache {
+    /// The cache at [`cache_path`] for this build, or `None` if there is
+    /// nowhere to keep it or the build has no [`version`].
+    pub fn open() -> Option<io::Result<Self>> {
+        let path = cache_path()?;
+        let version = version()?;
+        Some(Cache::load(path, version))
+    }
+
+    /// The cache at `path` for build `version`, empty if the file does not
+    /// exist yet.
+    pub fn load(path: PathBuf, version: String) -> io::Result<Self> {
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Cache {
+            path,
+            version,
+            entries,
+        })
+    }
+
+    /// Writes the cache back, dropping the answers of all but the
+    /// [`MAX_VERSIONS`] most recently used builds.
+    pub fn save(&mut self) -> io::Result<()> {
+        // This build's answers go last, so the entries run from the least to
+        // the most recently used build.
+        let (mut entries, current): (Vec<_>, Vec<_>) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(|entry| entry.version != self.version);
+        entries.extend(current);
+        let mut kept = Vec::new();
+        for entry in entries.iter().rev() {
+            if kept.len() < MAX_VERSIONS && !kept.contains(&entry.version) {
+                kept.push(entry.version.clone());
+            }
+        }
+        entries.retain(|entry| kept.contains(&entry.version));
+        self.entries = entries;
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut json = serde_json::to_string_pretty(&self.entries)?;
+        json.push('\n');
+        // Write through a temporary file so an interrupted save never leaves
+        // a truncated cache behind.
+        let partial = self.path.with_extension("part");
+        std::fs::write(&partial, json)?;
+        std::fs::rename(&partial, &self.path)
+    }
+
+    fn position(&self, day: u8, part: Part, input_hash: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| {
+            (entry.day, entry.part) == (day, part.number())
+                && entry.input_hash == input_hash
+                && entry.version == self.version
+        })
+    }
+
+    pub fn get(&self, day: u8, part: Part, input_hash: u64) -> Option<Answer> {
+        let entry = &self.entries[self.position(day, part, &hash_key(input_hash))?];
+        Answer::parse(&entry.answer_type, &entry.answer)
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, input_hash: u64, answer: Answer) {
+        let input_hash = hash_key(input_hash);
+        let entry = Entry {
+            day,
+            part: part.number(),
+            input_hash: input_hash.clone(),
+            version: self.version.clone(),
+            answer_type: answer.type_name().to_string(),
+            answer: answer.to_string(),
+        };
+        match self.position(day, part, &input_hash) {
+            Some(i) => self.entries[i] = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// The days other than `day` with an answer for the input hashed to
+    /// `input_hash`, from any build.
+    pub fn other_days(&self, day: u8, input_hash: u64) -> Vec<u8> {
+        let input_hash = hash_key(input_hash);
+        let mut days = self
+            .entries
+            .iter()
+            .filter(|entry| entry.day != day && entry.input_hash == input_hash)
+            .map(|entry| entry.day)
+            .collect::<Vec<_>>();
+        days.sort_unstable();
+        days.dedup();
+        days
+    }
+
+    /// Adds the answers `run` solved rather than took from the cache.
+    pub fn record(&mut self, run: &DayRun) {
+        let (Some(input_hash), Ok(parts)) = (run.input_hash, &run.parts) else {
+            return;
+        };
+        for part in parts.iter().filter(|part| !part.cached) {
+            if let Ok(answer) = part.answer {
+                self.insert(run.solution.day(), part.part, input_hash, answer);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aoc-2025/answers.json");
+        let mut cache = Cache::load(path.clone(), "0.1.0+a".to_string()).unwrap();
+        assert_eq!(cache.get(1, Part::One, 7), None);
+
+        cache.insert(1, Part::One, 7, Answer::U32(3));
+        cache.insert(1, Part::One, 7, Answer::U32(4));
+        cache.insert(1, Part::Two, 7, Answer::I32(-6));
+        cache.save().unwrap();
+
+        let mut cache = Cache::load(path.clone(), "0.1.0+a".to_string()).unwrap();
+        assert_eq!(cache.get(1, Part::One, 7), Some(Answer::U32(4)));
+        assert_eq!(cache.get(1, Part::Two, 7), Some(Answer::I32(-6)));
+        assert_eq!(cache.get(1, Part::One, 8), None);
+        assert_eq!(cache.get(2, Part::One, 7), None);
+        assert_eq!(cache.entries.len(), 2);
+
+        let mut other = Cache::load(path.clone(), "0.1.0+b".to_string()).unwrap();
+        assert_eq!(other.get(1, Part::One, 7), None);
+        assert_eq!(other.other_days(5, 7), [1]);
+        assert!(other.other_days(1, 7).is_empty());
+        other.insert(5, Part::One, 7, Answer::U64(1));
+        other.save().unwrap();
+
+        cache = Cache::load(path.clone(), "0.1.0+a".to_string()).unwrap();
+        assert_eq!(cache.get(1, Part::One, 7), Some(Answer::U32(4)));
+        assert_eq!(cache.other_days(1, 7), [5]);
+        assert!(!path.with_extension("part").exists());
+    }
+
+    #[test]
+    fn test_least_recently_used_builds_are_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.json");
+        let load = |version: usize| Cache::load(path.clone(), version.to_string()).unwrap();
+        for version in 0..MAX_VERSIONS {
+            let mut cache = load(version);
+            cache.insert(1, Part::One, 7, Answer::Usize(version));
+            cache.save().unwrap();
+        }
+        // Using the first build again makes the second the least recent.
+        load(0).save().unwrap();
+        let mut cache = load(MAX_VERSIONS);
+        cache.insert(1, Part::One, 7, Answer::Usize(MAX_VERSIONS));
+        cache.save().unwrap();
+
+        assert_eq!(load(0).get(1, Part::One, 7), Some(Answer::Usize(0)));
+        assert_eq!(load(1).get(1, Part::One, 7), None);
+        assert_eq!(load(2).get(1, Part::One, 7), Some(Answer::Usize(2)));
+        assert_eq!(load(0).entries.len(), MAX_VERSIONS);
+    }
+}
@@ -0,0 +1,538 @@
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::solution::Part;
+
+pub const YEAR: u16 = 2025;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = concat!("github.com/davidkna/aoc-2025 v", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum ClientError {
+    Http(ureq::Error),
+    Status {
+        status: u16,
+        body: String,
+    },
+    Io(io::Error),
+    NoSession(PathBuf),
+    /// A submission response that matched none of the known verdicts.
+    UnknownResponse(String),
+    /// A line of the submissions log that could not be read back.
+    Log {
+        line: usize,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Http(e) => e.fmt(f),
+            ClientError::Status { status, body } => {
+                write!(f, "server responded with {status}: {}", body.trim())
+            }
+            ClientError::Io(e) => e.fmt(f),
+            ClientError::NoSession(path) => write!(
+                f,
+                "no session token: set AOC_SESSION or write it to {}",
+                path.display()
+            ),
+            ClientError::UnknownResponse(body) => {
+                write!(f, "unrecognized response to submission: {}", body.trim())
+            }
+            ClientError::Log { line, source } => {
+                write!(f, "invalid submissions log entry on line {line}: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        ClientError::Http(e)
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+/// Default location of the session token, next to `inputs/`.
+pub fn session_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(".session")
+}
+
+/// Session token from `path`, else the `AOC_SESSION` environment variable,
+/// else [`session_path`].
+pub fn read_session(path: Option<&Path>) -> Result<String, ClientError> {
+    if path.is_none()
+        && let Ok(session) = std::env::var("AOC_SESSION")
+        && !session.trim().is_empty()
+    {
+        return Ok(session.trim().to_string());
+    }
+
+    let path = path.map_or_else(session_path, Path::to_path_buf);
+    match std::fs::read_to_string(&path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        Ok(_) => Err(ClientError::NoSession(path)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(ClientError::NoSession(path)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Authenticated client for the puzzle site rooted at `base_url`.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: impl Into<String>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+        Client {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.into(),
+        }
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{YEAR}/day/{day}", self.base_url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<Vec<u8>, ClientError> {
+        let mut response = self
+            .agent
+            .get(format!("{}/input", self.day_url(day)))
+            .header("Cookie", self.cookie())
+            .call()?;
+
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_vec()?;
+        if status != 200 {
+            return Err(ClientError::Status {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+        Ok(body)
+    }
+
+    pub fn submit_answer(&self, day: u8, part: Part, answer: &str) -> Result<Verdict, ClientError> {
+        let mut response = self
+            .agent
+            .post(format!("{}/answer", self.day_url(day)))
+            .header("Cookie", self.cookie())
+            .send_form([
+                ("level", part.number().to_string().as_str()),
+                ("answer", answer),
+            ])?;
+
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+        if status != 200 {
+            return Err(ClientError::Status { status, body });
+        }
+        Verdict::parse(&body).ok_or(ClientError::UnknownResponse(body))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded,
+    /// The input already existed on disk and was left untouched.
+    Cached,
+}
+
+/// Downloads the input for `day` to `path` unless the file already exists.
+pub fn fetch_to(client: &Client, day: u8, path: &Path) -> Result<Fetched, ClientError> {
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = client.fetch_input(day)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // Write through a temporary file so an interrupted download never
+    // leaves a truncated input behind to be mistaken for the cache.
+    let partial = path.with_extension("part");
+    std::fs::write(&partial, input)?;
+    std::fs::rename(&partial, path)?;
+    Ok(Fetched::Downloaded)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The site refused to look at the answer; nothing is recorded so it can
+    /// be resubmitted once the wait is over.
+    #[serde(skip)]
+    RateLimited {
+        wait: Option<String>,
+    },
+    /// The part was already solved on the site, which says nothing about
+    /// this answer, so it is not recorded either.
+    #[serde(skip)]
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the HTML page returned for a submission.
+    pub fn parse(body: &str) -> Option<Verdict> {
+        if body.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if body.contains("answer too recently") {
+            let wait = body.split_once("You have ").and_then(|(_, rest)| {
+                let (wait, _) = rest.split_once(" left to wait")?;
+                Some(wait.to_string())
+            });
+            Some(Verdict::RateLimited { wait })
+        } else if body.contains("not the right answer") {
+            Some(if body.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if body.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            })
+        } else if body.contains("solving the right level") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_correct(&self) -> bool {
+        *self == Verdict::Correct
+    }
+
+    /// Whether the part needs no more submissions: the answer was right, or
+    /// the part had already been solved.
+    pub fn is_done(&self) -> bool {
+        matches!(self, Verdict::Correct | Verdict::AlreadySolved)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("correct"),
+            Verdict::Incorrect => f.write_str("incorrect"),
+            Verdict::TooHigh => f.write_str("incorrect (too high)"),
+            Verdict::TooLow => f.write_str("incorrect (too low)"),
+            Verdict::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, try again in {wait}")
+            }
+            Verdict::RateLimited { wait: None } => f.write_str("rate limited"),
+            Verdict::AlreadySolved => f.write_str("already solved"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Default location of the submissions log, next to `inputs/`.
+pub fn submissions_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("submissions.jsonl")
+}
+
+/// Append-only log of every answer the site has judged, one JSON object per
+/// line.
+pub struct Submissions {
+    path: PathBuf,
+    entries: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Reads the log at `path`; a missing file is an empty log.
+    pub fn load(path: &Path) -> Result<Self, ClientError> {
+        let mut entries = Vec::new();
+        match std::fs::File::open(path) {
+            Ok(file) => {
+                for (i, line) in BufReader::new(file).lines().enumerate() {
+                    let line = line?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let entry = serde_json::from_str(&line).map_err(|source| ClientError::Log {
+                        line: i + 1,
+                        source,
+                    })?;
+                    entries.push(entry);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        Ok(Submissions {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// The verdict `answer` is already known to get, along with the earlier
+    /// submission that decides it: the same answer sent before, a correct
+    /// answer for the part, or a too-high/too-low bound it falls outside of.
+    pub fn known(&self, day: u8, part: Part, answer: &str) -> Option<(Verdict, &Submission)> {
+        let entries = || {
+            self.entries
+                .iter()
+                .filter(move |s| s.day == day && s.part == part.number())
+        };
+
+        if let Some(s) = entries().find(|s| s.answer == answer) {
+            return Some((s.verdict.clone(), s));
+        }
+        if let Some(s) = entries().find(|s| s.verdict.is_correct()) {
+            return Some((Verdict::Incorrect, s));
+        }
+
+        let value = answer.parse::<i128>().ok()?;
+        entries().find_map(|s| {
+            let bound = s.answer.parse::<i128>().ok()?;
+            match s.verdict {
+                Verdict::TooHigh if value >= bound => Some((Verdict::TooHigh, s)),
+                Verdict::TooLow if value <= bound => Some((Verdict::TooLow, s)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Appends `submission` to the log. Rate-limited attempts and answers to
+    /// parts already solved were never judged and are not recorded.
+    pub fn record(&mut self, submission: Submission) -> io::Result<()> {
+        if matches!(
+            submission.verdict,
+            Verdict::RateLimited { .. } | Verdict::AlreadySolved
+        ) {
+            return Ok(());
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let line = serde_json::to_string(&submission).map_err(io::Error::other)?;
+        writeln!(file, "{line}")?;
+        self.entries.push(submission);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+    };
+
+    /// Serves one canned `(status, body)` response per connection on a local
+    /// port and returns the base URL plus a handle yielding the raw requests.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: \
+                     close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch_downloads_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inputs/day07.txt");
+        let (base_url, server) = stub::serve(vec![(200, "..S..\n")]);
+        let client = Client::new(&format!("{base_url}/"), "abc123");
+
+        assert_eq!(fetch_to(&client, 7, &path).unwrap(), Fetched::Downloaded);
+        assert_eq!(std::fs::read(&path).unwrap(), b"..S..\n");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2025/day/7/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("session=abc123"));
+
+        // The stub is gone, so a second request would fail.
+        assert_eq!(fetch_to(&client, 7, &path).unwrap(), Fetched::Cached);
+    }
+
+    #[test]
+    fn test_fetch_error_leaves_no_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day01.txt");
+        let (base_url, server) = stub::serve(vec![(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        )]);
+        let client = Client::new(&base_url, "expired");
+
+        let err = fetch_to(&client, 1, &path).unwrap_err();
+        assert!(matches!(err, ClientError::Status { status: 400, .. }));
+        assert!(!path.exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let (base_url, server) = stub::serve(vec![
+            (
+                200,
+                "<article><p>That's not the right answer; your answer is too low. Please wait one \
+                 minute before trying again.</p></article>",
+            ),
+            (
+                200,
+                "<article><p>You gave an answer too recently; you have to wait after submitting \
+                 an answer before trying again.  You have 34s left to wait.</p></article>",
+            ),
+            (
+                200,
+                "<article><p>That's the right answer!  You are one gold star closer to decorating \
+                 the North Pole.</p></article>",
+            ),
+        ]);
+        let client = Client::new(&base_url, "abc123");
+
+        assert_eq!(
+            client.submit_answer(3, Part::Two, "100").unwrap(),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            client.submit_answer(3, Part::Two, "200").unwrap(),
+            Verdict::RateLimited {
+                wait: Some("34s".to_string())
+            }
+        );
+        assert!(
+            client
+                .submit_answer(3, Part::Two, "200")
+                .unwrap()
+                .is_correct()
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2025/day/3/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("session=abc123"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=100"));
+    }
+
+    #[test]
+    fn test_submissions_known() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.jsonl");
+        let mut log = Submissions::load(&path).unwrap();
+        let submission = |answer: &str, verdict| Submission {
+            day: 5,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+        };
+
+        log.record(submission("10", Verdict::TooLow)).unwrap();
+        log.record(submission("50", Verdict::TooHigh)).unwrap();
+        log.record(submission("30", Verdict::RateLimited { wait: None }))
+            .unwrap();
+        log.record(submission("20", Verdict::Incorrect)).unwrap();
+        log.record(submission("40", Verdict::AlreadySolved))
+            .unwrap();
+
+        let log = Submissions::load(&path).unwrap();
+        let known = |answer| {
+            log.known(5, Part::One, answer)
+                .map(|(v, s)| (v, s.answer.clone()))
+        };
+        assert_eq!(known("20"), Some((Verdict::Incorrect, "20".to_string())));
+        assert_eq!(known("7"), Some((Verdict::TooLow, "10".to_string())));
+        assert_eq!(known("50"), Some((Verdict::TooHigh, "50".to_string())));
+        assert_eq!(known("30"), None);
+        assert_eq!(known("40"), None);
+        assert_eq!(log.entries.len(), 3);
+        assert_eq!(log.known(5, Part::Two, "20"), None);
+    }
+
+    #[test]
+    fn test_read_session_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session");
+        std::fs::write(&path, "abc123\n").unwrap();
+
+        assert_eq!(read_session(Some(&path)).unwrap(), "abc123");
+        assert!(matches!(
+            read_session(Some(&dir.path().join("missing"))),
+            Err(ClientError::NoSession(_))
+        ));
+    }
+}
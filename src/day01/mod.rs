@@ -1,84 +1,194 @@
+pub mod reference;
+
+use std::io;
+
 use bstr::ByteSlice;
 
+use crate::{
+    ParseError, parse,
+    solution::{Answers, Params, Part, Solution, Timed},
+    stream::{self, StreamError},
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day01.txt");
 
-fn parse_uint(s: &[u8]) -> i32 {
-    s.iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as i32)
+const DAY: u8 = 1;
+
+fn parse_move(input: &[u8], line: &[u8]) -> Result<i32, ParseError> {
+    let (sign, delta) = match line {
+        [b'L', delta @ ..] => (-1, delta),
+        [b'R', delta @ ..] => (1, delta),
+        _ => return Err(ParseError::new(DAY, input, line, "`L` or `R`")),
+    };
+    parse::expect_uint::<i32>(DAY, input, delta, "a distance").map(|delta| sign * delta)
+}
+
+/// The dial rotations, each with its line for errors.
+pub struct Input<'a> {
+    raw: &'a [u8],
+    /// Clicks to turn, negative for left.
+    pub moves: Vec<(&'a [u8], i32)>,
+}
+
+impl<'a> Input<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        let moves = input
+            .lines()
+            .map(|line| Ok((line, parse_move(input, line)?)))
+            .collect::<Result<_, _>>()?;
+        Ok(Input { raw: input, moves })
+    }
+}
+
+/// Where the dial points after turning `delta` clicks from `pos`.
+fn turn(pos: i32, delta: i32) -> i32 {
+    (pos + delta % 100).rem_euclid(100)
+}
+
+/// How many times turning `delta` clicks from `pos` lands on zero.
+fn clicks_onto_zero(pos: i32, delta: i32) -> i32 {
+    let dist = delta.abs();
+
+    if dist == 0 {
+        return 0;
+    }
+
+    let dist_to_zero = if delta < 0 {
+        if pos == 0 { 100 } else { pos }
+    } else {
+        100 - pos
+    };
+
+    1 + (dist - dist_to_zero).div_floor(100)
+}
+
+fn too_many_clicks(input: &[u8], line: &[u8]) -> ParseError {
+    ParseError::new(DAY, input, line, "fewer than 2^31 clicks onto zero")
 }
 
-pub fn part_1(input: &[u8]) -> u32 {
+pub fn part_1(input: &Input) -> u32 {
     input
-        .lines()
-        .fold((50, 0), |(sum, zero_count), line| {
-            let out = match line {
-                [b'L', delta @ ..] => sum - parse_uint(delta),
-                [b'R', delta @ ..] => sum + parse_uint(delta),
-                _ => unreachable!(),
-            }
-            .rem_euclid(100);
-            (out, zero_count + (out == 0) as i32)
+        .moves
+        .iter()
+        .fold((50, 0), |(pos, zero_count), &(_, delta)| {
+            let out = turn(pos, delta);
+            (out, zero_count + (out == 0) as u32)
         })
-        .1 as u32
+        .1
 }
 
-pub fn part_2(input: &[u8]) -> i32 {
+pub fn part_2(input: &Input) -> Result<i32, ParseError> {
     input
-        .lines()
-        .fold((50, 0), |(pos, past_crossings), line| {
-            let dir = line[0];
-            let dist: i32 = parse_uint(&line[1..]);
+        .moves
+        .iter()
+        .try_fold((50, 0), |(pos, past_crossings), &(line, delta)| {
+            let crossings = i32::checked_add(past_crossings, clicks_onto_zero(pos, delta))
+                .ok_or_else(|| too_many_clicks(input.raw, line))?;
+            Ok((turn(pos, delta), crossings))
+        })
+        .map(|(_, crossings)| crossings)
+}
 
-            if dist == 0 {
-                return (pos, past_crossings);
+/// Both parts of the input in `reader`, in one pass and bounded memory.
+pub fn solve_reader(reader: impl io::BufRead) -> Result<(u32, i32), StreamError> {
+    let map = |chunk: &[u8]| {
+        chunk
+            .lines()
+            .map(|line| parse_move(chunk, line))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let (_, zero_count, crossings) = stream::try_fold(
+        reader,
+        (50, 0, 0),
+        map,
+        |(mut pos, mut zero_count, mut crossings), chunk, moves| {
+            let chunk = chunk.bytes();
+            for (line, delta) in chunk.lines().zip(moves) {
+                crossings = i32::checked_add(crossings, clicks_onto_zero(pos, delta))
+                    .ok_or_else(|| too_many_clicks(chunk, line))?;
+                pos = turn(pos, delta);
+                zero_count += (pos == 0) as u32;
             }
+            Ok((pos, zero_count, crossings))
+        },
+    )?;
+    Ok((zero_count, crossings))
+}
+
+pub fn try_part_1(input: &[u8]) -> Result<u32, ParseError> {
+    Input::parse(input).map(|input| part_1(&input))
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<i32, ParseError> {
+    part_2(&Input::parse(input)?)
+}
 
-            let delta = match dir {
-                b'L' => -dist,
-                b'R' => dist,
-                _ => unreachable!(),
-            };
+pub struct Day01;
 
-            let dist_to_zero = if delta < 0 {
-                if pos == 0 { 100 } else { pos }
-            } else {
-                100 - pos
-            };
+impl Solution for Day01 {
+    fn day(&self) -> u8 {
+        DAY
+    }
 
-            let new_crossings = 1 + (dist - dist_to_zero).div_floor(100);
-            let new_pos = (pos + delta).rem_euclid(100);
+    fn title(&self) -> &'static str {
+        "Secret Entrance"
+    }
 
-            (new_pos, past_crossings + new_crossings)
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, |input| Ok(part_1(input))),
+            Part::Two => Timed::measure(input, Input::parse, part_2),
         })
-        .1
+    }
+
+    fn solve_reader(&self, reader: &mut dyn io::BufRead) -> Option<Result<Answers, StreamError>> {
+        Some(solve_reader(reader).map(|(part_1, part_2)| {
+            vec![
+                (Part::One, Ok(part_1.into())),
+                (Part::Two, Ok(part_2.into())),
+            ]
+        }))
+    }
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
-    println!("Part 2: {}", part_2(INPUT));
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!("Part 1: {}", part_1(&input));
+    println!(
+        "Part 2: {}",
+        part_2(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &[u8] = b"L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82";
+    crate::example_tests!(DAY);
+
+    #[test]
+    fn test_parse_error() {
+        let err = try_part_1(b"L68\nL30\nX48").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
 
     #[test]
-    fn test_part_1() {
-        assert_eq!(part_1(EXAMPLE), 3);
+    fn test_long_rotations() {
+        assert_eq!(try_part_1(b"R2147483550").unwrap(), 1);
+        assert_eq!(try_part_2(b"R2147483550").unwrap(), 21474836);
+        let err = try_part_2(&b"R2147483647\n".repeat(101)).unwrap_err();
+        assert_eq!(err.line, 101);
     }
 
     #[test]
-    fn test_part_2() {
-        assert_eq!(part_2(EXAMPLE), 6);
+    fn test_solve_reader() {
+        let input =
+            std::fs::read(crate::examples::examples_dir().join("day01/example.txt")).unwrap();
+        assert_eq!(solve_reader(&input[..]).unwrap(), (3, 6));
+        let Err(StreamError::Parse(err)) = solve_reader(&b"L68\nL30\nX48"[..]) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column), (3, 1));
     }
 }
@@ -0,0 +1,32 @@
+//! Turns the dial one click at a time.
+
+fn moves(input: &[u8]) -> impl Iterator<Item = (i32, u32)> + '_ {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    input.lines().map(|line| {
+        let (direction, distance) = line.split_at(1);
+        let step = if direction == "L" { -1 } else { 1 };
+        (step, distance.parse().expect("distance is a number"))
+    })
+}
+
+pub fn part_1(input: &[u8]) -> u32 {
+    let mut dial = 50;
+    let mut zeros = 0;
+    for (step, distance) in moves(input) {
+        dial = (dial + step * distance as i32).rem_euclid(100);
+        zeros += (dial == 0) as u32;
+    }
+    zeros
+}
+
+pub fn part_2(input: &[u8]) -> i32 {
+    let mut dial = 50;
+    let mut zeros = 0;
+    for (step, distance) in moves(input) {
+        for _ in 0..distance {
+            dial = (dial + step).rem_euclid(100);
+            zeros += (dial == 0) as i32;
+        }
+    }
+    zeros
+}
@@ -1,11 +1,17 @@
+pub mod reference;
+
 use bstr::ByteSlice;
-use rayon::prelude::*;
 
+use crate::{
+    ParseError, parse,
+    solution::{Params, Part, Solution, Timed},
+    threads,
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day02.txt");
 
-fn parse_uint(s: &[u8]) -> u64 {
-    s.iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as u64)
-}
+const DAY: u8 = 2;
 
 fn is_invalid_part1(s: &[u8]) -> bool {
     let len = s.len();
@@ -44,68 +50,142 @@ fn increment(s: &mut Vec<u8>) {
     s.insert(0, b'1');
 }
 
-fn compare(a: &[u8], b: &[u8]) -> std::cmp::Ordering {
-    match a.len().cmp(&b.len()) {
-        std::cmp::Ordering::Equal => a.cmp(b),
-        other => other,
-    }
+fn overflow(input: &[u8], at: &[u8]) -> ParseError {
+    ParseError::new(
+        DAY,
+        input,
+        at,
+        "ranges whose invalid IDs sum to less than 2^64",
+    )
 }
 
-pub fn solve(input: &[u8], part2: bool) -> u64 {
-    input
-        .split_str(b",")
-        .par_bridge()
-        .map(|range| {
-            let (start, end) = range.split_once_str(b"-").unwrap();
+/// An inclusive range of product IDs.
+pub struct IdRange<'a> {
+    text: &'a [u8],
+    pub start: u64,
+    pub end: u64,
+}
 
-            let mut current = start.to_vec();
-            let mut total = 0;
+/// The product ID ranges.
+pub struct Input<'a> {
+    raw: &'a [u8],
+    pub ranges: Vec<IdRange<'a>>,
+}
 
-            while compare(&current, end) <= std::cmp::Ordering::Equal {
-                let invalid = if part2 {
-                    is_invalid_part2(&current)
-                } else {
-                    is_invalid_part1(&current)
-                };
+impl<'a> Input<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        let ranges = input
+            .trim_ascii_end()
+            .split_str(b",")
+            .map(|range| {
+                let (start, end) = range
+                    .split_once_str(b"-")
+                    .ok_or_else(|| ParseError::new(DAY, input, range, "a `-` separated range"))?;
+                Ok(IdRange {
+                    text: range,
+                    start: parse::expect_uint(DAY, input, start, "a product ID")?,
+                    end: parse::expect_uint(DAY, input, end, "a product ID")?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Input { raw: input, ranges })
+    }
+}
 
-                if invalid {
-                    total += parse_uint(&current);
-                }
-                increment(&mut current);
+pub fn solve(input: &Input, part2: bool) -> Result<u64, ParseError> {
+    threads::map(&input.ranges, |range| {
+        // The digits of `id`, without any leading zeros the bound had.
+        let mut current = range.start.to_string().into_bytes();
+        let mut total = 0u64;
+
+        for id in range.start..=range.end {
+            let invalid = if part2 {
+                is_invalid_part2(&current)
+            } else {
+                is_invalid_part1(&current)
+            };
+
+            if invalid {
+                total = total
+                    .checked_add(id)
+                    .ok_or_else(|| overflow(input.raw, range.text))?;
             }
-            total
-        })
-        .sum()
+            increment(&mut current);
+        }
+        Ok(total)
+    })
+    .into_iter()
+    .try_fold(0u64, |a, b| {
+        a.checked_add(b?)
+            .ok_or_else(|| overflow(input.raw, input.raw.trim_ascii_end()))
+    })
 }
 
-pub fn part_1(input: &[u8]) -> u64 {
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
     solve(input, false)
 }
 
-pub fn part_2(input: &[u8]) -> u64 {
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
     solve(input, true)
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
-    println!("Part 2: {}", part_2(INPUT));
+pub fn try_solve(input: &[u8], part2: bool) -> Result<u64, ParseError> {
+    solve(&Input::parse(input)?, part2)
+}
+
+pub fn try_part_1(input: &[u8]) -> Result<u64, ParseError> {
+    try_solve(input, false)
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    try_solve(input, true)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Gift Shop"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, part_1),
+            Part::Two => Timed::measure(input, Input::parse, part_2),
+        })
+    }
+}
+
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!(
+        "Part 1: {}",
+        part_1(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
+    println!(
+        "Part 2: {}",
+        part_2(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &[u8] = b"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+    crate::example_tests!(DAY);
 
     #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE);
-        assert_eq!(result, 1227775554);
+    fn test_parse_error() {
+        let err = try_part_1(b"11-22,95x115").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 7));
     }
 
     #[test]
-    fn test_part_2() {
-        let result = part_2(EXAMPLE);
-        assert_eq!(result, 4174379265);
+    fn test_leading_zeros() {
+        assert_eq!(try_part_1(b"0010-000000000000000000000022").unwrap(), 33);
     }
 }
@@ -0,0 +1,27 @@
+//! Checks every ID in every range as a decimal string.
+
+fn ids(input: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    input.trim_end().split(',').flat_map(|range| {
+        let (start, end) = range.split_once('-').expect("range has a `-`");
+        start.parse().expect("start is a number")..=end.parse().expect("end is a number")
+    })
+}
+
+/// Whether `id` is some digit sequence written out `times` times in a row.
+fn repeats(id: &str, times: usize) -> bool {
+    id.len().is_multiple_of(times) && id[..id.len() / times].repeat(times) == id
+}
+
+pub fn part_1(input: &[u8]) -> u64 {
+    ids(input).filter(|id| repeats(&id.to_string(), 2)).sum()
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    ids(input)
+        .filter(|id| {
+            let id = id.to_string();
+            (2..=id.len()).any(|times| repeats(&id, times))
+        })
+        .sum()
+}
@@ -1,7 +1,20 @@
+pub mod reference;
+
+use std::io;
+
 use bstr::ByteSlice;
 
+use crate::{
+    ParseError,
+    solution::{Answer, Answers, Params, Part, Solution, Timed},
+    stream::{self, Chunk, StreamError},
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day03.txt");
 
+const DAY: u8 = 3;
+
 fn solve_line(line: &[u8], size: usize) -> u64 {
     let mut max_item = line[0];
     let mut max_item_idx = 0;
@@ -23,37 +36,171 @@ fn solve_line(line: &[u8], size: usize) -> u64 {
         + solve_line(&line[max_item_idx + 1..], size - 1)
 }
 
-pub fn part_1(input: &[u8]) -> u64 {
-    input.lines().map(|line| solve_line(line, 2)).sum()
+/// The battery banks, as their joltage digits.
+pub struct Input<'a> {
+    raw: &'a [u8],
+    pub banks: Vec<&'a [u8]>,
+}
+
+impl<'a> Input<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        let banks = input
+            .lines()
+            .map(|line| match line.iter().position(|c| !c.is_ascii_digit()) {
+                Some(pos) => Err(ParseError::new(
+                    DAY,
+                    input,
+                    &line[pos..pos + 1],
+                    "a joltage digit",
+                )),
+                None => Ok(line),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Input { raw: input, banks })
+    }
+}
+
+fn too_much_joltage(input: &[u8], at: &[u8]) -> ParseError {
+    ParseError::new(DAY, input, at, "banks whose joltages sum to less than 2^64")
+}
+
+fn sum_banks(input: &Input, size: usize) -> Result<u64, ParseError> {
+    input
+        .banks
+        .iter()
+        .map(|&bank| {
+            if bank.len() < size {
+                return Err(ParseError::new(
+                    DAY,
+                    input.raw,
+                    bank,
+                    format!("a bank of at least {size} batteries"),
+                ));
+            }
+            Ok((bank, solve_line(bank, size)))
+        })
+        .try_fold(0u64, |total, bank| {
+            let (bank, joltage) = bank?;
+            total
+                .checked_add(joltage)
+                .ok_or_else(|| too_much_joltage(input.raw, bank))
+        })
+}
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    sum_banks(input, 2)
+}
+
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    sum_banks(input, 12)
+}
+
+/// One part's joltage summed over some banks.
+type Sum = Result<u64, ParseError>;
+
+/// Adds a chunk's `sum` for one part to the `total` of the chunks before it,
+/// keeping the first error.
+fn add_chunk(total: Sum, chunk: &Chunk, sum: Sum) -> Sum {
+    let sum = sum.map_err(|e| chunk.locate(e))?;
+    total?.checked_add(sum).ok_or_else(|| {
+        let bytes = chunk.bytes();
+        chunk.locate(too_much_joltage(bytes, bytes.trim_ascii_end()))
+    })
 }
 
-pub fn part_2(input: &[u8]) -> u64 {
-    input.lines().map(|line| solve_line(line, 12)).sum()
+/// Both parts of the input in `reader`, in one pass and bounded memory. Only
+/// errors in reading or parsing fail both parts.
+pub fn solve_reader(reader: impl io::BufRead) -> Result<(Sum, Sum), StreamError> {
+    stream::try_fold(
+        reader,
+        (Ok(0), Ok(0)),
+        |chunk| {
+            let banks = Input::parse(chunk)?;
+            Ok((part_1(&banks), part_2(&banks)))
+        },
+        |(total_1, total_2), chunk, (sum_1, sum_2)| {
+            Ok((
+                add_chunk(total_1, chunk, sum_1),
+                add_chunk(total_2, chunk, sum_2),
+            ))
+        },
+    )
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
-    println!("Part 2: {}", part_2(INPUT));
+pub fn try_part_1(input: &[u8]) -> Result<u64, ParseError> {
+    part_1(&Input::parse(input)?)
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    part_2(&Input::parse(input)?)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Lobby"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, part_1),
+            Part::Two => Timed::measure(input, Input::parse, part_2),
+        })
+    }
+
+    fn solve_reader(&self, reader: &mut dyn io::BufRead) -> Option<Result<Answers, StreamError>> {
+        Some(solve_reader(reader).map(|(part_1, part_2)| {
+            vec![
+                (Part::One, part_1.map(Answer::from)),
+                (Part::Two, part_2.map(Answer::from)),
+            ]
+        }))
+    }
+}
+
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!(
+        "Part 1: {}",
+        part_1(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
+    println!(
+        "Part 2: {}",
+        part_2(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &[u8] = b"987654321111111
-811111111111119
-234234234234278
-818181911112111";
+    crate::example_tests!(DAY);
 
     #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE);
-        assert_eq!(result, 357);
+    fn test_parse_error() {
+        let err = try_part_1(b"987654321111111\n81111a").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
     }
 
     #[test]
-    fn test_part_2() {
-        let result = part_2(EXAMPLE);
-        assert_eq!(result, 3121910778619);
+    fn test_solve_reader() {
+        let input = b"987654321111111\n811111111111119\n";
+        let (part_1, part_2) = solve_reader(&input[..]).unwrap();
+        assert_eq!(part_1.unwrap(), try_part_1(input).unwrap());
+        assert_eq!(part_2.unwrap(), try_part_2(input).unwrap());
+
+        let input = b"987654321111111\n81\n";
+        let (part_1, part_2) = solve_reader(&input[..]).unwrap();
+        assert_eq!(part_1.unwrap(), try_part_1(input).unwrap());
+        assert_eq!(part_2.unwrap_err().line, 2);
+        let Err(StreamError::Parse(err)) = solve_reader(&b"987654321111111\n81111a"[..]) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column), (2, 6));
     }
 }
@@ -0,0 +1,29 @@
+//! Tries every way of switching on `size` batteries in a bank.
+
+use itertools::Itertools;
+
+fn max_joltage(bank: &str, size: usize) -> u64 {
+    let digits = bank.as_bytes();
+    (0..digits.len())
+        .combinations(size)
+        .map(|picked| {
+            picked
+                .into_iter()
+                .fold(0, |acc, i| acc * 10 + u64::from(digits[i] - b'0'))
+        })
+        .max()
+        .expect("bank holds at least `size` batteries")
+}
+
+fn sum_banks(input: &[u8], size: usize) -> u64 {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    input.lines().map(|bank| max_joltage(bank, size)).sum()
+}
+
+pub fn part_1(input: &[u8]) -> u64 {
+    sum_banks(input, 2)
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    sum_banks(input, 12)
+}
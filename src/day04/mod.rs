@@ -1,92 +1,56 @@
-use bstr::ByteSlice;
-use itertools::Itertools;
+pub mod reference;
 
+use crate::{
+    ParseError,
+    grid::{Grid, Pos},
+    solution::{Params, Part, Solution, Timed},
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day04.txt");
 
-pub fn part_1(input: &[u8]) -> usize {
-    let map = input.lines().collect_vec();
-    let mut total = 0;
+const DAY: u8 = 4;
 
-    for y in 0..map.len() {
-        for x in 0..map[0].len() {
-            let c = map[y][x];
-            if c != b'@' {
-                continue;
-            }
-            let directions = [
-                (-1, -1),
-                (-1, 0),
-                (-1, 1),
-                (0, -1),
-                (0, 1),
-                (1, -1),
-                (1, 0),
-                (1, 1),
-            ];
-            let is_accessible = directions
-                .iter()
-                .filter(|(dy, dx)| {
-                    let ny = y as isize + dy;
-                    let nx = x as isize + dx;
-                    if ny < 0 || ny >= map.len() as isize || nx < 0 || nx >= map[0].len() as isize {
-                        return false;
-                    }
-                    let nc = map[ny as usize][nx as usize];
-                    nc == b'@'
-                })
-                .count()
-                < 4;
-            if is_accessible {
-                total += 1;
-            }
-        }
+/// The floor plan, with `@` for each roll of paper.
+pub struct Input {
+    pub map: Grid<u8>,
+}
+
+impl Input {
+    pub fn parse(input: &[u8]) -> Result<Self, ParseError> {
+        let map = Grid::parse(DAY, input, "`@` or `.`", |c| {
+            matches!(c, b'@' | b'.').then_some(c)
+        })?;
+        Ok(Input { map })
     }
-    total
 }
 
-pub fn part_2(input: &[u8]) -> u64 {
-    let mut map = input.lines().map(|line| line.to_vec()).collect_vec();
+fn is_accessible(map: &Grid<u8>, pos: Pos) -> bool {
+    map[pos] == b'@'
+        && map
+            .neighbours8(pos)
+            .filter(|&neighbour| map[neighbour] == b'@')
+            .count()
+            < 4
+}
+
+pub fn part_1(input: &Input) -> usize {
+    let map = &input.map;
+    map.positions()
+        .filter(|&pos| is_accessible(map, pos))
+        .count()
+}
+
+pub fn part_2(input: &Input) -> u64 {
+    let mut map = input.map.clone();
     let mut total_total = 0;
 
     loop {
         let mut total = 0;
-        for y in 0..map.len() {
-            for x in 0..map[0].len() {
-                let c = map[y][x];
-                if c != b'@' {
-                    continue;
-                }
-                let directions = [
-                    (-1, -1),
-                    (-1, 0),
-                    (-1, 1),
-                    (0, -1),
-                    (0, 1),
-                    (1, -1),
-                    (1, 0),
-                    (1, 1),
-                ];
-                let is_accessible = directions
-                    .iter()
-                    .filter(|(dy, dx)| {
-                        let ny = y as isize + dy;
-                        let nx = x as isize + dx;
-                        if ny < 0
-                            || ny >= map.len() as isize
-                            || nx < 0
-                            || nx >= map[0].len() as isize
-                        {
-                            return false;
-                        }
-                        let nc = map[ny as usize][nx as usize];
-                        nc == b'@'
-                    })
-                    .count()
-                    < 4;
-                if is_accessible {
-                    total += 1;
-                    map[y][x] = b'.';
-                }
+        for pos in map.positions() {
+            if is_accessible(&map, pos) {
+                total += 1;
+                map[pos] = b'.';
             }
         }
         if total == 0 {
@@ -97,35 +61,48 @@ pub fn part_2(input: &[u8]) -> u64 {
     total_total
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
-    println!("Part 2: {}", part_2(INPUT));
+pub fn try_part_1(input: &[u8]) -> Result<usize, ParseError> {
+    Input::parse(input).map(|input| part_1(&input))
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    Input::parse(input).map(|input| part_2(&input))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Printing Department"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, |input| Ok(part_1(input))),
+            Part::Two => Timed::measure(input, Input::parse, |input| Ok(part_2(input))),
+        })
+    }
+}
+
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!("Part 1: {}", part_1(&input));
+    println!("Part 2: {}", part_2(&input));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &[u8] = b"..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.";
-
-    #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE);
-        assert_eq!(result, 13);
-    }
+    crate::example_tests!(DAY);
 
     #[test]
-    fn test_part_2() {
-        let result = part_2(EXAMPLE);
-        assert_eq!(result, 43);
+    fn test_parse_error() {
+        let err = try_part_1(b"..@@\n@@x.").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
     }
 }
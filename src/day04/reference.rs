@@ -0,0 +1,53 @@
+//! Removes every accessible roll at once, round after round.
+
+use std::collections::HashSet;
+
+fn rolls(input: &[u8]) -> HashSet<(i32, i32)> {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    let mut rolls = HashSet::new();
+    for (y, line) in input.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            if c == '@' {
+                rolls.insert((x as i32, y as i32));
+            }
+        }
+    }
+    rolls
+}
+
+fn accessible(rolls: &HashSet<(i32, i32)>) -> Vec<(i32, i32)> {
+    rolls
+        .iter()
+        .copied()
+        .filter(|&(x, y)| {
+            let mut neighbours = 0;
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if (dx, dy) != (0, 0) && rolls.contains(&(x + dx, y + dy)) {
+                        neighbours += 1;
+                    }
+                }
+            }
+            neighbours < 4
+        })
+        .collect()
+}
+
+pub fn part_1(input: &[u8]) -> usize {
+    accessible(&rolls(input)).len()
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    let mut rolls = rolls(input);
+    let mut removed = 0;
+    loop {
+        let round = accessible(&rolls);
+        if round.is_empty() {
+            return removed;
+        }
+        removed += round.len() as u64;
+        for roll in round {
+            rolls.remove(&roll);
+        }
+    }
+}
@@ -1,10 +1,35 @@
+pub mod reference;
+
+use std::io;
+
 use bstr::ByteSlice;
 use itertools::Itertools;
 
+use crate::{
+    ParseError, parse,
+    repl::{self, Explore},
+    solution::{Answers, Params, Part, Solution, Timed},
+    stream::{self, StreamError},
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day05.txt");
 
-fn parse_uint(s: &[u8]) -> u64 {
-    s.iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as u64)
+const DAY: u8 = 5;
+
+fn missing_blank_line(input: &[u8]) -> ParseError {
+    ParseError::new(
+        DAY,
+        input,
+        &input[input.len()..],
+        "a blank line before the ingredient IDs",
+    )
+}
+
+fn split_sections(input: &[u8]) -> Result<(&[u8], &[u8]), ParseError> {
+    input
+        .split_once_str("\n\n")
+        .ok_or_else(|| missing_blank_line(input))
 }
 
 pub struct IntervalMap {
@@ -29,7 +54,8 @@ impl IntervalMap {
         IntervalMap { intervals }
     }
 
-    fn contains(&self, point: u64) -> bool {
+    /// The merged interval holding `point`, if any.
+    pub fn find(&self, point: u64) -> Option<(u64, u64)> {
         let result = self.intervals.binary_search_by(|&(start, end)| {
             if point < start {
                 std::cmp::Ordering::Greater
@@ -39,76 +65,228 @@ impl IntervalMap {
                 std::cmp::Ordering::Equal
             }
         });
-        result.is_ok()
+        result.ok().map(|i| self.intervals[i])
     }
 
-    fn total_interval_length(&self) -> u64 {
-        self.intervals
-            .iter()
-            .map(|(start, end)| end - start + 1)
-            .sum()
+    pub fn contains(&self, point: u64) -> bool {
+        self.find(point).is_some()
+    }
+
+    /// `None` if every single ID is fresh, which is one more than fits.
+    pub fn total_interval_length(&self) -> Option<u64> {
+        self.intervals.iter().try_fold(0u64, |total, (start, end)| {
+            total.checked_add((end - start).checked_add(1)?)
+        })
     }
 }
 
-pub fn parse_input(input: &[u8]) -> IntervalMap {
-    let intervals = input.lines().map(|line| {
-        let (start, end) = line.split_once_str(b"-").unwrap();
-        let start = parse_uint(start);
-        let end = parse_uint(end);
-        (start, end)
-    });
+fn parse_ranges(input: &[u8], ranges: &[u8]) -> Result<IntervalMap, ParseError> {
+    let intervals = ranges
+        .lines()
+        .map(|line| {
+            let (start_raw, end_raw) = line
+                .split_once_str(b"-")
+                .ok_or_else(|| ParseError::new(DAY, input, line, "a `-` separated range"))?;
+            let start = parse::expect_uint(DAY, input, start_raw, "an ingredient ID")?;
+            let end = parse::expect_uint(DAY, input, end_raw, "an ingredient ID")?;
+            if end < start {
+                return Err(ParseError::new(
+                    DAY,
+                    input,
+                    end_raw,
+                    format!("a range end of at least {start}"),
+                ));
+            }
+            Ok((start, end))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
 
-    IntervalMap::from_iter(intervals)
+    Ok(IntervalMap::from_iter(intervals.into_iter()))
 }
 
-pub fn part_1(input: &[u8]) -> usize {
-    let (ranges, ingredients) = input.split_once_str("\n\n").unwrap();
-    let imap = parse_input(ranges);
+/// The fresh ID ranges, merged, and the available ingredient IDs.
+pub struct Input<'a> {
+    raw: &'a [u8],
+    ranges: &'a [u8],
+    pub fresh: IntervalMap,
+    pub ingredients: Vec<u64>,
+}
 
-    ingredients
-        .lines()
-        .map(parse_uint)
-        .filter(|&num| imap.contains(num))
+impl<'a> Input<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        let (ranges, ingredients) = split_sections(input)?;
+        let fresh = parse_ranges(input, ranges)?;
+        let ingredients = ingredients
+            .lines()
+            .map(|line| parse::expect_uint(DAY, input, line, "an ingredient ID"))
+            .collect::<Result<_, _>>()?;
+        Ok(Input {
+            raw: input,
+            ranges,
+            fresh,
+            ingredients,
+        })
+    }
+}
+
+pub fn part_1(input: &Input) -> usize {
+    input
+        .ingredients
+        .iter()
+        .filter(|&&id| input.fresh.contains(id))
         .count()
 }
 
-pub fn part_2(input: &[u8]) -> u64 {
-    let (ranges, _ingredients) = input.split_once_str("\n\n").unwrap();
-    let imap = parse_input(ranges);
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    input.fresh.total_interval_length().ok_or_else(|| {
+        ParseError::new(
+            DAY,
+            input.raw,
+            input.ranges,
+            "ranges that leave some ID spoiled",
+        )
+    })
+}
+
+/// Both parts of the input in `reader`, in one pass. Only the ranges are
+/// kept in memory, the ingredient IDs are checked as they are read.
+pub fn solve_reader(mut reader: impl io::BufRead) -> Result<(usize, u64), StreamError> {
+    let mut ranges = Vec::new();
+    let mut lines = 0;
+    loop {
+        let start = ranges.len();
+        if reader.read_until(b'\n', &mut ranges)? == 0 {
+            return Err(missing_blank_line(&ranges).into());
+        }
+        lines += 1;
+        if ranges[start..] == *b"\n" {
+            break;
+        }
+    }
+    let ranges = &ranges[..ranges.len() - 1];
 
-    imap.total_interval_length()
+    let fresh = parse_ranges(ranges, ranges)?;
+    let total = fresh
+        .total_interval_length()
+        .ok_or_else(|| ParseError::new(DAY, ranges, ranges, "ranges that leave some ID spoiled"))?;
+    let count = stream::try_fold_from(
+        reader,
+        lines + 1,
+        0,
+        |chunk| {
+            chunk.lines().try_fold(0, |count, line| {
+                let id = parse::expect_uint(DAY, chunk, line, "an ingredient ID")?;
+                Ok(count + fresh.contains(id) as usize)
+            })
+        },
+        |total, _, count| Ok(total + count),
+    )?;
+    Ok((count, total))
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
-    println!("Part 2: {}", part_2(INPUT));
+pub fn try_part_1(input: &[u8]) -> Result<usize, ParseError> {
+    Input::parse(input).map(|input| part_1(&input))
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    part_2(&Input::parse(input)?)
+}
+
+impl Explore for Input<'_> {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            (
+                "query ID",
+                "whether ID is fresh, and the merged range holding it",
+            ),
+            ("ranges", "how many merged ranges and IDs there are"),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        Some(match command {
+            "query" => repl::arg(args, 0, "ID").map(|id: u64| match self.fresh.find(id) {
+                Some((start, end)) => format!("{id} is fresh, in {start}-{end}"),
+                None => format!("{id} is spoiled"),
+            }),
+            "ranges" => Ok(format!(
+                "{} merged ranges, {} IDs",
+                self.fresh.intervals.len(),
+                self.fresh
+                    .total_interval_length()
+                    .map_or("2^64".to_string(), |n| n.to_string())
+            )),
+            _ => return None,
+        })
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Cafeteria"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, |input| Ok(part_1(input))),
+            Part::Two => Timed::measure(input, Input::parse, part_2),
+        })
+    }
+
+    fn solve_reader(&self, reader: &mut dyn io::BufRead) -> Option<Result<Answers, StreamError>> {
+        Some(solve_reader(reader).map(|(part_1, part_2)| {
+            vec![
+                (Part::One, Ok(part_1.into())),
+                (Part::Two, Ok(part_2.into())),
+            ]
+        }))
+    }
+
+    fn explore<'a>(&self, input: &'a [u8]) -> Option<Result<Box<dyn Explore + 'a>, ParseError>> {
+        Some(Input::parse(input).map(|input| Box::new(input) as Box<dyn Explore>))
+    }
+}
+
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!("Part 1: {}", part_1(&input));
+    println!(
+        "Part 2: {}",
+        part_2(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &[u8] = b"3-5
-10-14
-16-20
-12-18
-
-1
-5
-8
-11
-17
-32";
+    crate::example_tests!(DAY);
 
     #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE);
-        assert_eq!(result, 3);
+    fn test_parse_error() {
+        let err = try_part_1(b"3-5\n10-14\n\n1\n5x").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 1));
     }
 
     #[test]
-    fn test_part_2() {
-        let result = part_2(EXAMPLE);
-        assert_eq!(result, 14);
+    fn test_solve_reader() {
+        assert_eq!(
+            solve_reader(&b"3-5\n10-14\n\n1\n5\n12\n"[..]).unwrap(),
+            (2, 8)
+        );
+        let Err(StreamError::Parse(err)) = solve_reader(&b"3-5\n10-14\n\n1\n5x"[..]) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column), (5, 1));
+        let Err(StreamError::Parse(err)) = solve_reader(&b"3-5\n10-14"[..]) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column), (2, 6));
     }
 }
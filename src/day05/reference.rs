@@ -0,0 +1,39 @@
+//! Tests IDs against every range and counts fresh IDs one by one.
+
+use std::collections::HashSet;
+
+type Ranges = Vec<(u64, u64)>;
+
+fn parse(input: &[u8]) -> (Ranges, Vec<u64>) {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    let (ranges, ids) = input.split_once("\n\n").expect("sections are separated");
+    let ranges = ranges
+        .lines()
+        .map(|line| {
+            let (start, end) = line.split_once('-').expect("range has a `-`");
+            (start.parse().unwrap(), end.parse().unwrap())
+        })
+        .collect();
+    let ids = ids.lines().map(|id| id.parse().unwrap()).collect();
+    (ranges, ids)
+}
+
+pub fn part_1(input: &[u8]) -> usize {
+    let (ranges, ids) = parse(input);
+    ids.into_iter()
+        .filter(|id| {
+            ranges
+                .iter()
+                .any(|(start, end)| (start..=end).contains(&id))
+        })
+        .count()
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    let (ranges, _) = parse(input);
+    let fresh = ranges
+        .into_iter()
+        .flat_map(|(start, end)| start..=end)
+        .collect::<HashSet<_>>();
+    fresh.len() as u64
+}
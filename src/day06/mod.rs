@@ -1,85 +1,156 @@
+pub mod reference;
+
 use bstr::ByteSlice;
 use itertools::Itertools;
 
+use crate::{
+    ParseError, parse,
+    solution::{Params, Part, Solution, Timed},
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day06.txt");
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-enum Operation {
+pub enum Operation {
     Add,
     Multiply,
 }
 
-pub fn part_1(input: &[u8]) -> u64 {
-    let (numbers, operations) = input.rsplit_once_str("\n").unwrap();
+const DAY: u8 = 6;
 
-    let numbers = numbers
-        .lines()
-        .map(|line| {
-            let (mut nums, last_num) =
-                line.iter()
-                    .fold((Vec::new(), None), |(mut nums, mut current), &c| {
-                        match c {
-                            b' ' if current.is_some() => {
-                                nums.push(current.take().unwrap());
-                            }
-                            b' ' => {}
-                            b'0'..=b'9' => {
-                                current = Some(current.unwrap_or(0) * 10 + (c - b'0') as u64);
-                            }
-                            _ => unreachable!(),
-                        }
-                        (nums, current)
-                    });
-            if let Some(num) = last_num {
-                nums.push(num);
+/// The worksheet: rows of digits and spaces, to be read across or down,
+/// over the operator of each problem.
+pub struct Input<'a> {
+    raw: &'a [u8],
+    pub rows: Vec<&'a [u8]>,
+    operations_line: &'a [u8],
+    pub operations: Vec<Operation>,
+}
+
+impl<'a> Input<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        let worksheet = input.strip_suffix(b"\n").unwrap_or(input);
+        let (numbers, operations_line) = worksheet.rsplit_once_str("\n").ok_or_else(|| {
+            ParseError::new(DAY, input, &input[input.len()..], "a row of operators")
+        })?;
+
+        let rows = numbers.lines().collect_vec();
+        for row in &rows {
+            if let Some(pos) = row.iter().position(|&c| c != b' ' && !c.is_ascii_digit()) {
+                return Err(ParseError::new(
+                    DAY,
+                    input,
+                    &row[pos..pos + 1],
+                    "a digit or a space",
+                ));
             }
-            nums
+        }
+
+        let operations = operations_line
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &c)| match c {
+                b'+' => Some(Ok(Operation::Add)),
+                b'*' => Some(Ok(Operation::Multiply)),
+                b' ' => None,
+                _ => Some(Err(ParseError::new(
+                    DAY,
+                    input,
+                    &operations_line[i..i + 1],
+                    "`+`, `*` or a space",
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Input {
+            raw: input,
+            rows,
+            operations_line,
+            operations,
         })
-        .collect_vec();
+    }
+}
 
-    let operations: Vec<Operation> = operations
+fn overflow(input: &[u8], operations_line: &[u8]) -> ParseError {
+    ParseError::new(
+        DAY,
+        input,
+        operations_line,
+        "problems whose answers fit in 64 bits",
+    )
+}
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    let Input {
+        raw: input,
+        rows,
+        operations_line,
+        operations,
+    } = input;
+
+    let numbers = rows
         .iter()
-        .filter_map(|&c| match c {
-            b'+' => Some(Operation::Add),
-            b'*' => Some(Operation::Multiply),
-            _ => None,
+        .map(|line| {
+            let nums = line
+                .fields()
+                .map(|num| parse::expect_uint::<u64>(DAY, input, num, "a number"))
+                .collect::<Result<Vec<_>, _>>()?;
+            if nums.len() != operations.len() {
+                return Err(ParseError::new(
+                    DAY,
+                    input,
+                    line,
+                    format!("{} numbers", operations.len()),
+                ));
+            }
+            Ok(nums)
         })
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let mut result = 0;
+    let mut result = 0u64;
     for (col, op) in operations.iter().enumerate() {
-        let col_result: u64 = if op == &Operation::Add {
-            numbers.iter().map(|nums| nums[col]).sum()
+        let mut column = numbers.iter().map(|nums| nums[col]);
+        let col_result = if op == &Operation::Add {
+            column.try_fold(0u64, u64::checked_add)
         } else {
-            numbers.iter().map(|nums| nums[col]).product()
+            column.try_fold(1u64, u64::checked_mul)
         };
-        result += col_result;
+        result = col_result
+            .and_then(|col_result| result.checked_add(col_result))
+            .ok_or_else(|| overflow(input, operations_line))?;
     }
 
-    result
+    Ok(result)
 }
 
-pub fn part_2(input: &[u8]) -> u64 {
-    let (numbers, operations) = input.rsplit_once_str("\n").unwrap();
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    let Input {
+        raw: input,
+        rows: numbers,
+        operations_line,
+        operations,
+    } = input;
+
+    let width = numbers.first().map_or(0, |row| row.len());
+    if let Some(row) = numbers.iter().find(|row| row.len() != width) {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            row,
+            format!("a row of width {width}"),
+        ));
+    }
 
-    let numbers = numbers.lines().collect_vec();
+    let checked = |value: Option<u64>| value.ok_or_else(|| overflow(input, operations_line));
 
-    let operations: Vec<Operation> = operations
-        .iter()
-        .filter_map(|&c| match c {
-            b'+' => Some(Operation::Add),
-            b'*' => Some(Operation::Multiply),
-            _ => None,
-        })
-        .collect();
-
-    let mut result = 0;
+    let mut result = 0u64;
     let mut op_iter = operations.iter();
     let mut op = None;
-    let mut col_result = 0;
-    for col in 0..numbers[0].len() {
+    let mut col_result = 0u64;
+    for col in 0..width {
         if col == 0 || numbers.iter().all(|nums| nums[col] == b' ') {
-            result += col_result;
+            result = checked(result.checked_add(col_result))?;
             op = op_iter.next();
             col_result = match op {
                 Some(Operation::Add) => 0,
@@ -91,47 +162,81 @@ pub fn part_2(input: &[u8]) -> u64 {
             }
         }
 
-        let col_num = numbers
-            .iter()
-            .map(|nums| nums[col])
-            .filter(|&n| n != b' ')
-            .fold(0, |acc, n| acc * 10 + (n - b'0') as u64);
+        let col_num = checked(
+            numbers
+                .iter()
+                .map(|nums| nums[col])
+                .filter(|&n| n != b' ')
+                .try_fold(0u64, |acc, n| {
+                    acc.checked_mul(10)?.checked_add((n - b'0') as u64)
+                }),
+        )?;
 
         match op {
-            Some(Operation::Add) => col_result += col_num,
-            Some(Operation::Multiply) => col_result *= col_num,
-            None => unreachable!(),
+            Some(Operation::Add) => col_result = checked(col_result.checked_add(col_num))?,
+            Some(Operation::Multiply) => col_result = checked(col_result.checked_mul(col_num))?,
+            None => {
+                return Err(ParseError::new(
+                    DAY,
+                    input,
+                    &operations_line[operations_line.len()..],
+                    "an operator for every problem",
+                ));
+            }
         }
     }
 
-    result + col_result
+    checked(result.checked_add(col_result))
+}
+
+pub fn try_part_1(input: &[u8]) -> Result<u64, ParseError> {
+    part_1(&Input::parse(input)?)
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    part_2(&Input::parse(input)?)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Trash Compactor"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, part_1),
+            Part::Two => Timed::measure(input, Input::parse, part_2),
+        })
+    }
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
-    println!("Part 2: {}", part_2(INPUT));
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!(
+        "Part 1: {}",
+        part_1(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
+    println!(
+        "Part 2: {}",
+        part_2(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &[u8] = concat_bytes!(
-        b"123 328  51 64 \n",
-        b" 45 64  387 23 \n",
-        b"  6 98  215 314\n",
-        b"*   +   *   +  "
-    );
-
-    #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE);
-        assert_eq!(result, 4277556);
-    }
+    crate::example_tests!(DAY);
 
     #[test]
-    fn test_part_2() {
-        let result = part_2(EXAMPLE);
-        assert_eq!(result, 3263827);
+    fn test_parse_error() {
+        let err = try_part_1(b"123 328\n 45 6a\n*   +  ").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
     }
 }
@@ -0,0 +1,78 @@
+//! Cuts the worksheet into problems at the blank columns and reads each one
+//! both ways.
+
+use std::ops::Range;
+
+struct Problem {
+    multiply: bool,
+    /// The problem's columns of each number row.
+    rows: Vec<String>,
+}
+
+fn problems(input: &[u8]) -> Vec<Problem> {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    let mut lines = input.lines().collect::<Vec<_>>();
+    let operators = lines.pop().expect("worksheet has an operator row");
+    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    let rows = lines
+        .iter()
+        .map(|line| format!("{line:width$}"))
+        .collect::<Vec<_>>();
+
+    let blank = |x: usize| rows.iter().all(|row| row.as_bytes()[x] == b' ');
+    let mut spans: Vec<Range<usize>> = Vec::new();
+    for x in 0..width {
+        match spans.last_mut() {
+            _ if blank(x) => {}
+            Some(span) if span.end == x => span.end += 1,
+            _ => spans.push(x..x + 1),
+        }
+    }
+
+    spans
+        .into_iter()
+        .map(|span| Problem {
+            multiply: operators[span.start..].trim_start().starts_with('*'),
+            rows: rows
+                .iter()
+                .map(|row| row[span.clone()].to_string())
+                .collect(),
+        })
+        .collect()
+}
+
+fn solve(numbers: impl Iterator<Item = u64>, multiply: bool) -> u64 {
+    if multiply {
+        numbers.product()
+    } else {
+        numbers.sum()
+    }
+}
+
+pub fn part_1(input: &[u8]) -> u64 {
+    problems(input)
+        .iter()
+        .map(|problem| {
+            let numbers = problem.rows.iter().map(|row| row.trim().parse().unwrap());
+            solve(numbers, problem.multiply)
+        })
+        .sum()
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    problems(input)
+        .iter()
+        .map(|problem| {
+            let width = problem.rows[0].len();
+            let numbers = (0..width).map(|x| {
+                let column = problem
+                    .rows
+                    .iter()
+                    .map(|row| row.as_bytes()[x] as char)
+                    .collect::<String>();
+                column.replace(' ', "").parse::<u64>().unwrap()
+            });
+            solve(numbers, problem.multiply)
+        })
+        .sum()
+}
@@ -1,30 +1,81 @@
+pub mod reference;
+
 use bstr::ByteSlice;
 
+use crate::{
+    ParseError,
+    grid::{self, Grid, Pos},
+    solution::{Params, Part, Solution, Timed},
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day07.txt");
 
-pub fn part_1(input: &[u8]) -> u64 {
-    let mut lines = input.lines();
-    let first_line = lines.next().unwrap();
-    let start = first_line.iter().position(|&c| c == b'S').unwrap();
+const DAY: u8 = 7;
 
-    let mut beams = vec![false; first_line.len()];
-    beams[start] = true;
+/// The tachyon manifold, with the splitters as `^`.
+pub struct Input<'a> {
+    raw: &'a [u8],
+    pub start: Pos,
+    pub grid: Grid<u8>,
+}
 
-    lines
+impl<'a> Input<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        let grid = Grid::parse(DAY, input, "`.`, `^` or `S`", |c| {
+            matches!(c, b'.' | b'^' | b'S').then_some(c)
+        })?;
+        let start = grid.find(&b'S').ok_or_else(|| {
+            let first_line = input.lines().next().unwrap_or_default();
+            ParseError::new(DAY, input, first_line, "a start position `S`")
+        })?;
+
+        // Beams split to both sides, so a splitter on the edge would send one
+        // of them out of the manifold.
+        let last = grid.width() - 1;
+        for x in [0, last] {
+            if let Some(y) = grid.column(x).position(|&c| c == b'^') {
+                return Err(ParseError::new(
+                    DAY,
+                    input,
+                    grid::source_cell(input, (x, y)),
+                    "a splitter away from the edge",
+                ));
+            }
+        }
+
+        Ok(Input {
+            raw: input,
+            start,
+            grid,
+        })
+    }
+}
+
+/// The rows below the start, which the beams travel through.
+fn rows_below<'a>(Input { start, grid, .. }: &'a Input) -> impl Iterator<Item = &'a [u8]> {
+    grid.rows().skip(start.1 + 1)
+}
+
+pub fn part_1(manifold: &Input) -> u64 {
+    let mut beams = vec![false; manifold.grid.width()];
+    beams[manifold.start.0] = true;
+
+    rows_below(manifold)
         .fold((beams, 0u64), |(beams, mut splits), line| {
-            let mut new_beams = beams.clone();
+            let mut new_beams = vec![false; beams.len()];
 
             for (i, &c) in line.iter().enumerate() {
-                if c == b'.' {
+                if !beams[i] {
                     continue;
                 }
-                if !beams[i] {
+                if c != b'^' {
+                    new_beams[i] = true;
                     continue;
                 }
                 splits += 1;
                 new_beams[i - 1] = true;
                 new_beams[i + 1] = true;
-                new_beams[i] = false;
             }
 
             (new_beams, splits)
@@ -32,70 +83,108 @@ pub fn part_1(input: &[u8]) -> u64 {
         .1
 }
 
-pub fn part_2(input: &[u8]) -> u64 {
-    let mut lines = input.lines();
-    let first_line = lines.next().unwrap();
-    let start = first_line.iter().position(|&c| c == b'S').unwrap();
+pub fn part_2(manifold: &Input) -> Result<u64, ParseError> {
+    let input = manifold.raw;
+
+    let mut beams = vec![0u64; manifold.grid.width()];
+    beams[manifold.start.0] = 1;
 
-    let mut beams = vec![0u64; first_line.len()];
-    beams[start] = 1;
+    let overflow = |pos| {
+        ParseError::new(
+            DAY,
+            input,
+            grid::source_cell(input, pos),
+            "a splitter reached by fewer than 2^64 timelines",
+        )
+    };
 
-    lines
-        .fold(beams, |beams: Vec<u64>, line| {
+    rows_below(manifold)
+        .zip(manifold.start.1 + 1..)
+        .try_fold(beams, |beams: Vec<u64>, (line, y)| {
             let mut new_beams = beams.clone();
 
             for (i, &c) in line.iter().enumerate() {
-                if c == b'.' {
+                if c != b'^' {
                     continue;
                 }
                 if beams[i] == 0 {
                     continue;
                 }
-                new_beams[i - 1] += beams[i];
-                new_beams[i + 1] += beams[i];
-                new_beams[i] = 0;
+                // Subtract rather than reset, a splitter to the left may
+                // already have sent timelines here.
+                for side in [i - 1, i + 1] {
+                    new_beams[side] = new_beams[side]
+                        .checked_add(beams[i])
+                        .ok_or_else(|| overflow((i, y)))?;
+                }
+                new_beams[i] -= beams[i];
             }
-            new_beams
-        })
+            Ok(new_beams)
+        })?
         .into_iter()
-        .sum()
+        .try_fold(0u64, u64::checked_add)
+        .ok_or_else(|| {
+            let last_line = input
+                .trim_ascii_end()
+                .lines()
+                .next_back()
+                .unwrap_or_default();
+            ParseError::new(DAY, input, last_line, "fewer than 2^64 timelines")
+        })
+}
+
+pub fn try_part_1(input: &[u8]) -> Result<u64, ParseError> {
+    Input::parse(input).map(|input| part_1(&input))
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    part_2(&Input::parse(input)?)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Laboratories"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, |input| Ok(part_1(input))),
+            Part::Two => Timed::measure(input, Input::parse, part_2),
+        })
+    }
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
-    println!("Part 2: {}", part_2(INPUT));
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!("Part 1: {}", part_1(&input));
+    println!(
+        "Part 2: {}",
+        part_2(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &[u8] = b".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............";
+    crate::example_tests!(DAY);
 
     #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE);
-        assert_eq!(result, 21);
+    fn test_parse_error() {
+        let err = try_part_1(b"..S..\n.....\n^....").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
     }
 
     #[test]
-    fn test_part_2() {
-        let result = part_2(EXAMPLE);
-        assert_eq!(result, 40);
+    fn test_adjacent_splitters() {
+        let input = b"..S..\n..^..\n...^.\n.^^..";
+        assert_eq!(try_part_1(input).unwrap(), 4);
+        assert_eq!(try_part_2(input).unwrap(), 5);
     }
 }
@@ -0,0 +1,47 @@
+//! Follows every beam and every timeline separately.
+
+fn parse(input: &[u8]) -> (usize, Vec<&[u8]>) {
+    let mut rows = input.split(|&c| c == b'\n').filter(|row| !row.is_empty());
+    let first = rows.next().expect("manifold has a first row");
+    let start = first
+        .iter()
+        .position(|&c| c == b'S')
+        .expect("start on the first row");
+    (start, rows.collect())
+}
+
+pub fn part_1(input: &[u8]) -> u64 {
+    let (start, rows) = parse(input);
+    let mut beams = vec![start];
+    let mut splits = 0;
+    for row in rows {
+        let mut next = Vec::new();
+        for beam in beams {
+            if row[beam] == b'^' {
+                splits += 1;
+                next.extend([beam - 1, beam + 1]);
+            } else {
+                next.push(beam);
+            }
+        }
+        next.sort_unstable();
+        next.dedup();
+        beams = next;
+    }
+    splits
+}
+
+fn timelines(rows: &[&[u8]], beam: usize) -> u64 {
+    match rows {
+        [] => 1,
+        [row, rest @ ..] if row[beam] == b'^' => {
+            timelines(rest, beam - 1) + timelines(rest, beam + 1)
+        }
+        [_, rest @ ..] => timelines(rest, beam),
+    }
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    let (start, rows) = parse(input);
+    timelines(&rows, start)
+}
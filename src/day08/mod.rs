@@ -1,35 +1,66 @@
 #![allow(clippy::needless_range_loop)]
 
+pub mod reference;
+
 use bstr::ByteSlice;
 use itertools::Itertools;
 
+use crate::{
+    ParseError, parse,
+    repl::{self, Explore},
+    solution::{Params, Part, Solution, Timed},
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day08.txt");
 
-fn parse_uint(s: &[u8]) -> u64 {
-    s.iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as u64)
+const DAY: u8 = 8;
+
+/// Connections part 1 makes on the real input.
+const STEPS: usize = 1000;
+
+/// The junction boxes' positions, at least two of them.
+pub struct Input {
+    pub coords: Vec<(u64, u64, u64)>,
 }
 
-fn euclidean_distance(a: (u64, u64, u64), b: (u64, u64, u64)) -> u64 {
-    let dx = (a.0 as i64 - b.0 as i64).pow(2);
-    let dy = (a.1 as i64 - b.1 as i64).pow(2);
-    let dz = (a.2 as i64 - b.2 as i64).pow(2);
-    // skip sqrt for performance reasons
-    (dx + dy + dz) as u64
+impl Input {
+    pub fn parse(input: &[u8]) -> Result<Self, ParseError> {
+        // 32-bit coordinates keep the product in part 2 within a `u64`.
+        let coordinate =
+            |s| parse::expect_uint::<u32>(DAY, input, s, "a 32-bit coordinate").map(u64::from);
+        let coords = input
+            .lines()
+            .map(|line| {
+                let [x, y, z] = line.splitn_str(3, ",").collect_array().ok_or_else(|| {
+                    ParseError::new(DAY, input, line, "three comma-separated coordinates")
+                })?;
+                Ok((coordinate(x)?, coordinate(y)?, coordinate(z)?))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if coords.len() < 2 {
+            return Err(ParseError::new(
+                DAY,
+                input,
+                &input[input.len()..],
+                "at least two junction boxes",
+            ));
+        }
+        Ok(Input { coords })
+    }
 }
 
-pub fn part_1(input: &[u8], steps: usize) -> usize {
-    let coords: Vec<(u64, u64, u64)> = input
-        .lines()
-        .map(|line| {
-            line.splitn_str(3, ",")
-                .collect_array()
-                .map(|[x, y, z]| (parse_uint(x), parse_uint(y), parse_uint(z)))
-                .unwrap()
-        })
-        .collect_vec();
+fn euclidean_distance(a: (u64, u64, u64), b: (u64, u64, u64)) -> u128 {
+    let squared = |a: u64, b: u64| u128::from(a.abs_diff(b)).pow(2);
+    // skip sqrt for performance reasons
+    squared(a.0, b.0) + squared(a.1, b.1) + squared(a.2, b.2)
+}
 
+/// Squared distances between every pair of boxes.
+fn distances(coords: &[(u64, u64, u64)]) -> Vec<Vec<u128>> {
     let n = coords.len();
-    let mut dist = vec![vec![u64::MAX; n]; n];
+    let mut dist = vec![vec![u128::MAX; n]; n];
 
     for i in 0..n {
         dist[i][i] = 0;
@@ -39,12 +70,19 @@ pub fn part_1(input: &[u8], steps: usize) -> usize {
             dist[j][i] = d;
         }
     }
+    dist
+}
+
+pub fn part_1(input: &Input, steps: usize) -> usize {
+    let coords = &input.coords;
+    let n = coords.len();
+    let mut dist = distances(coords);
 
     let mut junction: Vec<Option<usize>> = vec![None; n];
     let mut next_junction_id = 0;
     for _ in 0..steps {
         // Find the closest pair of nodes
-        let mut min_dist = u64::MAX;
+        let mut min_dist = u128::MAX;
         let mut min_i = 0;
         let mut min_j = 0;
         for i in 0..n {
@@ -79,8 +117,8 @@ pub fn part_1(input: &[u8], steps: usize) -> usize {
         junction[min_i] = Some(junction_id);
         junction[min_j] = Some(junction_id);
 
-        dist[min_i][min_j] = u64::MAX;
-        dist[min_j][min_i] = u64::MAX;
+        dist[min_i][min_j] = u128::MAX;
+        dist[min_j][min_i] = u128::MAX;
     }
 
     // Three largest junctions
@@ -98,34 +136,16 @@ pub fn part_1(input: &[u8], steps: usize) -> usize {
         .product()
 }
 
-pub fn part_2(input: &[u8]) -> u64 {
-    let coords: Vec<(u64, u64, u64)> = input
-        .lines()
-        .map(|line| {
-            line.splitn_str(3, ",")
-                .collect_array()
-                .map(|[x, y, z]| (parse_uint(x), parse_uint(y), parse_uint(z)))
-                .unwrap()
-        })
-        .collect_vec();
-
+pub fn part_2(input: &Input) -> u64 {
+    let coords = &input.coords;
     let n = coords.len();
-    let mut dist = vec![vec![u64::MAX; n]; n];
-
-    for i in 0..n {
-        dist[i][i] = 0;
-        for j in (i + 1)..n {
-            let d = euclidean_distance(coords[i], coords[j]);
-            dist[i][j] = d;
-            dist[j][i] = d;
-        }
-    }
+    let mut dist = distances(coords);
 
     let mut junction: Vec<Option<usize>> = vec![None; n];
     let mut next_junction_id = 0;
     loop {
         // Find the closest pair of nodes
-        let mut min_dist = u64::MAX;
+        let mut min_dist = u128::MAX;
         let mut min_i = 0;
         let mut min_j = 0;
         for i in 0..n {
@@ -138,18 +158,6 @@ pub fn part_2(input: &[u8]) -> u64 {
             }
         }
 
-        let j1 = junction[min_i].or(junction[min_j]);
-        let j2 = junction[min_j].or(junction[min_i]);
-        if j1.is_some()
-            && junction
-                .iter()
-                .enumerate()
-                .filter(|&(idx, _)| idx != min_i && idx != min_j)
-                .all(|(_, j)| *j == j1 || *j == j2)
-        {
-            return coords[min_i].0 * coords[min_j].0;
-        }
-
         // Create a new junction
         let junction_id = match (junction[min_i], junction[min_j]) {
             (Some(id), Some(id2)) => {
@@ -171,59 +179,115 @@ pub fn part_2(input: &[u8]) -> u64 {
 
         junction[min_i] = Some(junction_id);
         junction[min_j] = Some(junction_id);
+        if junction.iter().all(|&j| j == Some(junction_id)) {
+            return coords[min_i].0 * coords[min_j].0;
+        }
+
         // Update distances
         for k in 0..n {
             let j_id = junction[k];
             if j_id != Some(junction_id) {
                 continue;
             }
-            dist[min_i][k] = u64::MAX;
-            dist[k][min_i] = u64::MAX;
-            dist[min_j][k] = u64::MAX;
-            dist[k][min_j] = u64::MAX;
+            dist[min_i][k] = u128::MAX;
+            dist[k][min_i] = u128::MAX;
+            dist[min_j][k] = u128::MAX;
+            dist[k][min_j] = u128::MAX;
+        }
+    }
+}
+
+impl Input {
+    fn index(&self, args: &[&str], i: usize) -> Result<usize, String> {
+        let index = repl::arg(args, i, "box")?;
+        if index < self.coords.len() {
+            Ok(index)
+        } else {
+            Err(format!("there are only {} boxes", self.coords.len()))
         }
     }
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT, 1000));
-    println!("Part 2: {}", part_2(INPUT));
+impl Explore for Input {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("box I", "the coordinates of the box on line I, from 0"),
+            ("nearest I", "the box closest to box I"),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        Some(match command {
+            "box" => self.index(args, 0).map(|i| {
+                let (x, y, z) = self.coords[i];
+                format!("{x},{y},{z}")
+            }),
+            "nearest" => self.index(args, 0).map(|i| {
+                let (j, distance) = (0..self.coords.len())
+                    .filter(|&j| j != i)
+                    .map(|j| (j, euclidean_distance(self.coords[i], self.coords[j])))
+                    .min_by_key(|&(_, distance)| distance)
+                    .expect("there are at least two boxes");
+                let (x, y, z) = self.coords[j];
+                format!("box {j} at {x},{y},{z}, squared distance {distance}")
+            }),
+            _ => return None,
+        })
+    }
+}
+
+pub fn try_part_1(input: &[u8], steps: usize) -> Result<usize, ParseError> {
+    Input::parse(input).map(|input| part_1(&input, steps))
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    Input::parse(input).map(|input| part_2(&input))
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Playground"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], params: &Params) -> Option<Timed> {
+        let steps = params.get("steps").copied().unwrap_or(STEPS);
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, |input| Ok(part_1(input, steps))),
+            Part::Two => Timed::measure(input, Input::parse, |input| Ok(part_2(input))),
+        })
+    }
+
+    fn explore<'a>(&self, input: &'a [u8]) -> Option<Result<Box<dyn Explore + 'a>, ParseError>> {
+        Some(Input::parse(input).map(|input| Box::new(input) as Box<dyn Explore>))
+    }
+}
+
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!("Part 1: {}", part_1(&input, STEPS));
+    println!("Part 2: {}", part_2(&input));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &[u8] = b"162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
+    crate::example_tests!(DAY);
 
     #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE, 10);
-        assert_eq!(result, 40);
+    fn test_parse_error() {
+        let err = try_part_2(b"162,817,812\n57,618").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
     }
 
     #[test]
-    fn test_part_2() {
-        let result = part_2(EXAMPLE);
-        assert_eq!(result, 25272);
+    fn test_two_boxes() {
+        assert_eq!(try_part_2(b"1,0,0\n2,0,0").unwrap(), 2);
     }
 }
@@ -0,0 +1,66 @@
+//! Sorts every pair of boxes by distance and merges circuits by relabelling.
+
+use itertools::Itertools;
+
+type Boxes = Vec<[i64; 3]>;
+
+fn parse(input: &[u8]) -> Boxes {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    input
+        .lines()
+        .map(|line| {
+            line.split(',')
+                .map(|c| c.parse().unwrap())
+                .collect_array()
+                .expect("three coordinates")
+        })
+        .collect()
+}
+
+/// Pairs of box indices from closest to farthest, ties in index order.
+fn pairs(boxes: &Boxes) -> Vec<(usize, usize)> {
+    let distance = |(i, j): (usize, usize)| -> i64 {
+        (0..3).map(|k| (boxes[i][k] - boxes[j][k]).pow(2)).sum()
+    };
+    (0..boxes.len())
+        .tuple_combinations()
+        .sorted_by_key(|&pair| (distance(pair), pair))
+        .collect()
+}
+
+/// Puts `j`'s circuit into `i`'s and returns the number of circuits left.
+fn connect(circuit: &mut [usize], i: usize, j: usize) -> usize {
+    let (from, to) = (circuit[j], circuit[i]);
+    for c in circuit.iter_mut() {
+        if *c == from {
+            *c = to;
+        }
+    }
+    circuit.iter().unique().count()
+}
+
+pub fn part_1(input: &[u8], steps: usize) -> usize {
+    let boxes = parse(input);
+    let mut circuit = (0..boxes.len()).collect_vec();
+    for (i, j) in pairs(&boxes).into_iter().take(steps) {
+        connect(&mut circuit, i, j);
+    }
+    circuit
+        .into_iter()
+        .counts()
+        .into_values()
+        .sorted_unstable_by(|a, b| b.cmp(a))
+        .take(3)
+        .product()
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    let boxes = parse(input);
+    let mut circuit = (0..boxes.len()).collect_vec();
+    for (i, j) in pairs(&boxes) {
+        if connect(&mut circuit, i, j) == 1 {
+            return (boxes[i][0] * boxes[j][0]) as u64;
+        }
+    }
+    panic!("boxes never form a single circuit")
+}
@@ -0,0 +1,60 @@
+//! Colours in the whole floor and checks every tile of every rectangle.
+
+use itertools::Itertools;
+
+fn parse(input: &[u8]) -> Vec<(i64, i64)> {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    input
+        .lines()
+        .map(|line| {
+            let (x, y) = line.split_once(',').unwrap();
+            (x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect()
+}
+
+fn area((x1, y1): (i64, i64), (x2, y2): (i64, i64)) -> u64 {
+    (x1.abs_diff(x2) + 1) * (y1.abs_diff(y2) + 1)
+}
+
+pub fn part_1(input: &[u8]) -> u64 {
+    parse(input)
+        .into_iter()
+        .tuple_combinations()
+        .map(|(a, b)| area(a, b))
+        .max()
+        .unwrap()
+}
+
+/// Whether the tile at `(x, y)` is on the loop of red and green tiles or
+/// inside it.
+fn coloured(red: &[(i64, i64)], (x, y): (i64, i64)) -> bool {
+    let edges = red.iter().copied().circular_tuple_windows::<(_, _)>();
+    let mut inside = false;
+    for ((x1, y1), (x2, y2)) in edges {
+        let on_edge =
+            (x1.min(x2)..=x1.max(x2)).contains(&x) && (y1.min(y2)..=y1.max(y2)).contains(&y);
+        if on_edge {
+            return true;
+        }
+        // Cast a ray to the right, counting the vertical edges it crosses.
+        if x1 == x2 && x1 > x && (y1.min(y2)..y1.max(y2)).contains(&y) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    let red = parse(input);
+    red.iter()
+        .tuple_combinations()
+        .filter(|&(&(x1, y1), &(x2, y2))| {
+            (x1.min(x2)..=x1.max(x2))
+                .cartesian_product(y1.min(y2)..=y1.max(y2))
+                .all(|tile| coloured(&red, tile))
+        })
+        .map(|(&a, &b)| area(a, b))
+        .max()
+        .unwrap()
+}
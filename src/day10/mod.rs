@@ -1,154 +1,364 @@
-use std::collections::VecDeque;
+pub mod reference;
+
+use std::{collections::VecDeque, io};
 
 use bitvec::prelude::*;
 use bstr::ByteSlice;
 use itertools::Itertools;
-use rayon::prelude::*;
 
+use crate::{
+    ParseError, parse,
+    solution::{Answers, Params, Part, Solution, Timed},
+    stream::{self, StreamError},
+    threads,
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day10.txt");
 
-fn parse_uint(s: &[u8]) -> u32 {
-    s.iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as u32)
+const DAY: u8 = 10;
+
+pub struct Machine {
+    pub lights: Vec<bool>,
+    /// The lights each button toggles, by index.
+    pub buttons: Vec<Vec<usize>>,
+    pub joltage: Vec<u64>,
+}
+
+/// The machines, each with its line for errors.
+pub struct Input<'a> {
+    raw: &'a [u8],
+    pub machines: Vec<(&'a [u8], Machine)>,
+}
+
+impl<'a> Input<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        let machines = threads::map(input.lines(), |line| {
+            parse_machine(input, line).map(|machine| (line, machine))
+        })
+        .into_iter()
+        .collect::<Result<_, _>>()?;
+        Ok(Input {
+            raw: input,
+            machines,
+        })
+    }
+}
+
+fn delimited<'a>(
+    input: &[u8],
+    token: &'a [u8],
+    open: u8,
+    close: u8,
+    expected: &'static str,
+) -> Result<&'a [u8], ParseError> {
+    match token {
+        [first, inner @ .., last] if *first == open && *last == close => Ok(inner),
+        _ => Err(ParseError::new(DAY, input, token, expected)),
+    }
 }
 
-pub fn part_1(input: &[u8]) -> u32 {
-    input
-        .lines()
-        .par_bridge()
-        .map(|line| {
-            let (target, rest) = line.split_once_str(" ").unwrap();
-            let target = &target[1..target.len() - 1]; // remove [ ]
-            let target = target.iter().fold(
-                BitVec::<u8, Msb0>::with_capacity(target.len()),
-                |mut bv, &c| {
-                    bv.push(c == b'#');
-                    bv
-                },
-            );
-
-            let (buttons, _joltage) = rest.rsplit_once_str(" ").unwrap();
-            let buttons = buttons.split_str(" ")
-            .map(|x| &x[1..x.len()-1]) // remove ( )
-            .map(|s| s.split_str(",").fold(bitvec![u8, Msb0; 0; target.len()], |mut bv, idx_bytes| {
-                let idx = parse_uint(idx_bytes) as usize;
-                bv.set(idx, true);
-                bv
-            }))
+fn parse_machine(input: &[u8], line: &[u8]) -> Result<Machine, ParseError> {
+    let (target, rest) = line
+        .split_once_str(" ")
+        .ok_or_else(|| ParseError::new(DAY, input, line, "buttons after the indicator lights"))?;
+    let target = delimited(input, target, b'[', b']', "`[` indicator lights `]`")?;
+    let lights = target
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| match c {
+            b'.' => Ok(false),
+            b'#' => Ok(true),
+            _ => Err(ParseError::new(DAY, input, &target[i..i + 1], "`.` or `#`")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (buttons, joltage) = rest
+        .rsplit_once_str(" ")
+        .ok_or_else(|| ParseError::new(DAY, input, rest, "joltage levels after the buttons"))?;
+    let buttons = buttons
+        .split_str(" ")
+        .map(|button| {
+            delimited(input, button, b'(', b')', "a `(` button wiring `)`")?
+                .split_str(",")
+                .map(|idx_bytes| {
+                    let idx: usize = parse::expect_uint(DAY, input, idx_bytes, "a light index")?;
+                    if idx >= lights.len() {
+                        return Err(ParseError::new(
+                            DAY,
+                            input,
+                            idx_bytes,
+                            format!("a light index below {}", lights.len()),
+                        ));
+                    }
+                    Ok(idx)
+                })
+                .collect()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let joltage_token = joltage;
+    let joltage = delimited(input, joltage, b'{', b'}', "`{` joltage levels `}`")?
+        .split_str(",")
+        .map(|x| parse::expect_uint::<u32>(DAY, input, x, "a 32-bit joltage level").map(u64::from))
+        .collect::<Result<Vec<_>, _>>()?;
+    if joltage.len() != lights.len() {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            joltage_token,
+            format!("{} joltage levels", lights.len()),
+        ));
+    }
+
+    Ok(Machine {
+        lights,
+        buttons,
+        joltage,
+    })
+}
+
+fn too_many_presses(input: &[u8], at: &[u8], bits: u32) -> ParseError {
+    ParseError::new(
+        DAY,
+        input,
+        at,
+        format!("machines needing fewer than 2^{bits} presses in all"),
+    )
+}
+
+pub fn part_1(input: &Input) -> Result<u32, ParseError> {
+    threads::map(&input.machines, |(line, machine)| {
+        let target = machine.lights.iter().collect::<BitVec<u8, Msb0>>();
+        if target.not_any() {
+            return Ok(0);
+        }
+
+        let buttons = machine
+            .buttons
+            .iter()
+            .map(|button| {
+                button
+                    .iter()
+                    .fold(bitvec![u8, Msb0; 0; target.len()], |mut bv, &idx| {
+                        bv.set(idx, true);
+                        bv
+                    })
+            })
             .collect_vec();
 
-            let current: BitVec<u8, Msb0> = bitvec![u8, Msb0; 0; target.len()];
-            let mut q: VecDeque<BitVec<u8, Msb0>> = VecDeque::new();
-            q.push_back(current);
-            let mut steps = 0u32;
-            while !q.is_empty() {
-                let level_size = q.len();
-                let mut level_seen = fnv::FnvHashSet::default();
-                for _ in 0..level_size {
-                    let state = q.pop_front().unwrap();
-                    for button in &buttons {
-                        let mut next_state = state.clone();
-                        next_state ^= button;
-                        if next_state == target {
-                            return steps + 1;
-                        }
-                        if level_seen.insert(next_state.clone()) {
-                            q.push_back(next_state);
-                        }
+        let current: BitVec<u8, Msb0> = bitvec![u8, Msb0; 0; target.len()];
+        let mut seen = fnv::FnvHashSet::default();
+        seen.insert(current.clone());
+        let mut q: VecDeque<BitVec<u8, Msb0>> = VecDeque::new();
+        q.push_back(current);
+        let mut steps = 0u32;
+        while !q.is_empty() {
+            let level_size = q.len();
+            for _ in 0..level_size {
+                let state = q.pop_front().unwrap();
+                for button in &buttons {
+                    let mut next_state = state.clone();
+                    next_state ^= button;
+                    if next_state == target {
+                        return Ok(steps + 1);
+                    }
+                    if seen.insert(next_state.clone()) {
+                        q.push_back(next_state);
                     }
                 }
-                steps += 1;
             }
-            unreachable!()
-        })
-        .sum()
+            steps += 1;
+        }
+        Err(ParseError::new(
+            DAY,
+            input.raw,
+            line,
+            "buttons that can switch on the indicator lights",
+        ))
+    })
+    .into_iter()
+    .zip(&input.machines)
+    .try_fold(0u32, |total, (presses, (line, _))| {
+        total
+            .checked_add(presses?)
+            .ok_or_else(|| too_many_presses(input.raw, line, 32))
+    })
 }
 
-pub fn part_2(input: &[u8]) -> u64 {
-    input
-        .lines()
-        .par_bridge()
-        .map(|line| {
-            let (_indicators, rest) = line.split_once_str(" ").unwrap();
-            let (buttons, joltage) = rest.rsplit_once_str(" ").unwrap();
-            let joltage = &joltage[1..joltage.len() - 1];
-            let target: Vec<u64> = joltage
-                .split_str(",")
-                .map(|x| parse_uint(x) as u64)
-                .collect_vec();
-
-            let buttons: Vec<Vec<u64>> = buttons
-                .split_str(" ")
-                .map(|x| &x[1..x.len() - 1])
-                .map(|s| {
-                    s.split_str(",")
-                        .fold(vec![0u64; target.len()], |mut v, idx_bytes| {
-                            let idx = parse_uint(idx_bytes) as usize;
-                            v[idx] = 1;
-                            v
-                        })
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    threads::map(&input.machines, |(line, machine)| {
+        let target = &machine.joltage;
+
+        let buttons: Vec<Vec<u64>> = machine
+            .buttons
+            .iter()
+            .map(|button| {
+                button.iter().fold(vec![0u64; target.len()], |mut v, &idx| {
+                    v[idx] = 1;
+                    v
                 })
-                .collect_vec();
+            })
+            .collect_vec();
+
+        let opt = z3::Optimize::new();
 
-            let opt = z3::Optimize::new();
+        let vars: Vec<z3::ast::Int> = buttons
+            .iter()
+            .enumerate()
+            .map(|(i, _)| z3::ast::Int::new_const(format!("x_{}", i)))
+            .collect_vec();
+
+        for var in &vars {
+            opt.assert(&var.ge(0));
+        }
 
-            let vars: Vec<z3::ast::Int> = buttons
+        for (idx, &tgt) in target.iter().enumerate() {
+            let sum_expr = buttons
                 .iter()
                 .enumerate()
-                .map(|(i, _)| z3::ast::Int::new_const(format!("x_{}", i)))
-                .collect_vec();
+                .map(|(i, button)| {
+                    let coeff = button[idx] as i64;
+                    z3::ast::Int::from_i64(coeff) * &vars[i]
+                })
+                .sum::<z3::ast::Int>();
+            opt.assert(&sum_expr.eq(z3::ast::Int::from_u64(tgt)));
+        }
 
-            for var in &vars {
-                opt.assert(&var.ge(0));
-            }
+        let total_presses = vars.iter().sum::<z3::ast::Int>();
+        opt.minimize(&total_presses);
 
-            for (idx, &tgt) in target.iter().enumerate() {
-                let sum_expr = buttons
-                    .iter()
-                    .enumerate()
-                    .map(|(i, button)| {
-                        let coeff = button[idx] as i64;
-                        z3::ast::Int::from_i64(coeff) * &vars[i]
-                    })
-                    .sum::<z3::ast::Int>();
-                opt.assert(&sum_expr.eq(z3::ast::Int::from_i64(tgt as i64)));
-            }
+        if opt.check(&[]) != z3::SatResult::Sat {
+            return Err(ParseError::new(
+                DAY,
+                input.raw,
+                line,
+                "buttons that can reach the joltage levels",
+            ));
+        }
+        Ok(opt
+            .get_model()
+            .unwrap()
+            .eval(&total_presses, true)
+            .unwrap()
+            .as_i64()
+            .unwrap() as u64)
+    })
+    .into_iter()
+    .zip(&input.machines)
+    .try_fold(0u64, |total, (presses, (line, _))| {
+        total
+            .checked_add(presses?)
+            .ok_or_else(|| too_many_presses(input.raw, line, 64))
+    })
+}
 
-            let total_presses = vars.iter().sum::<z3::ast::Int>();
-            opt.minimize(&total_presses);
+/// Both parts of the input in `reader`, in one pass and bounded memory.
+pub fn solve_reader(reader: impl io::BufRead) -> Result<(u32, u64), StreamError> {
+    stream::try_fold(
+        reader,
+        (0, 0),
+        |chunk| {
+            let machines = Input::parse(chunk)?;
+            Ok((part_1(&machines)?, part_2(&machines)?))
+        },
+        |(total_1, total_2): (u32, u64), chunk, (sum_1, sum_2)| {
+            let chunk = chunk.bytes();
+            let at = chunk.trim_ascii_end();
+            Ok((
+                total_1
+                    .checked_add(sum_1)
+                    .ok_or_else(|| too_many_presses(chunk, at, 32))?,
+                total_2
+                    .checked_add(sum_2)
+                    .ok_or_else(|| too_many_presses(chunk, at, 64))?,
+            ))
+        },
+    )
+}
+
+pub fn try_part_1(input: &[u8]) -> Result<u32, ParseError> {
+    part_1(&Input::parse(input)?)
+}
 
-            opt.check(&[]);
-            opt.get_model()
-                .unwrap()
-                .eval(&total_presses, true)
-                .unwrap()
-                .as_i64()
-                .unwrap() as u64
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    part_2(&Input::parse(input)?)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Factory"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, part_1),
+            Part::Two => Timed::measure(input, Input::parse, part_2),
         })
-        .sum()
+    }
+
+    fn solve_reader(&self, reader: &mut dyn io::BufRead) -> Option<Result<Answers, StreamError>> {
+        Some(solve_reader(reader).map(|(part_1, part_2)| {
+            vec![
+                (Part::One, Ok(part_1.into())),
+                (Part::Two, Ok(part_2.into())),
+            ]
+        }))
+    }
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
-    println!("Part 2: {}", part_2(INPUT));
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!(
+        "Part 1: {}",
+        part_1(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
+    println!(
+        "Part 2: {}",
+        part_2(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &[u8] = b"[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+    crate::example_tests!(DAY);
+
+    #[test]
+    fn test_parse_error() {
+        let err = try_part_1(b"[.##.] (3) (1,9) {3,5,4,7}").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 15));
+    }
+
+    #[test]
+    fn test_lights_already_off() {
+        assert_eq!(try_part_1(b"[..] (0) (0,1) {1,1}").unwrap(), 0);
+    }
 
     #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE);
-        assert_eq!(result, 7);
+    fn test_unreachable() {
+        let err = try_part_1(b"[.#] (0) {1,0}").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
     }
 
     #[test]
-    fn test_part_2() {
-        let result = part_2(EXAMPLE);
-        assert_eq!(result, 33);
+    fn test_solve_reader() {
+        let input = b"[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}\n[..] (0) (0,1) {1,1}";
+        assert_eq!(
+            solve_reader(&input[..]).unwrap(),
+            (try_part_1(input).unwrap(), try_part_2(input).unwrap())
+        );
+        let Err(StreamError::Parse(err)) = solve_reader(&b"[..] (0) {1,1}\n[.#] (0) {1,0}"[..])
+        else {
+            panic!("expected an error");
+        };
+        assert_eq!((err.line, err.column), (2, 1));
     }
 }
@@ -0,0 +1,86 @@
+//! Tries every combination of button presses.
+
+struct Machine {
+    lights: Vec<bool>,
+    buttons: Vec<Vec<usize>>,
+    joltage: Vec<u64>,
+}
+
+fn parse(input: &[u8]) -> Vec<Machine> {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    let numbers = |s: &str| -> Vec<u64> {
+        s[1..s.len() - 1]
+            .split(',')
+            .map(|n| n.parse().unwrap())
+            .collect()
+    };
+    input
+        .lines()
+        .map(|line| {
+            let tokens = line.split(' ').collect::<Vec<_>>();
+            let (lights, rest) = tokens.split_first().unwrap();
+            let (joltage, buttons) = rest.split_last().unwrap();
+            Machine {
+                lights: lights[1..lights.len() - 1]
+                    .chars()
+                    .map(|c| c == '#')
+                    .collect(),
+                buttons: buttons
+                    .iter()
+                    .map(|b| numbers(b).into_iter().map(|i| i as usize).collect())
+                    .collect(),
+                joltage: numbers(joltage),
+            }
+        })
+        .collect()
+}
+
+/// Pressing a button twice undoes it, so each button is pressed at most once.
+fn fewest_toggles(machine: &Machine) -> u32 {
+    (0u32..1 << machine.buttons.len())
+        .filter(|pressed| {
+            let mut lights = vec![false; machine.lights.len()];
+            for (i, button) in machine.buttons.iter().enumerate() {
+                if pressed & (1 << i) != 0 {
+                    for &light in button {
+                        lights[light] = !lights[light];
+                    }
+                }
+            }
+            lights == machine.lights
+        })
+        .map(u32::count_ones)
+        .min()
+        .expect("lights can be reached")
+}
+
+/// Presses `buttons[0]` every possible number of times and recurses on the
+/// rest, returning the fewest total presses that hit `joltage` exactly.
+fn fewest_presses(buttons: &[Vec<usize>], joltage: &mut [u64]) -> Option<u64> {
+    let Some((button, rest)) = buttons.split_first() else {
+        return joltage.iter().all(|&j| j == 0).then_some(0);
+    };
+    let most = button.iter().map(|&i| joltage[i]).min().unwrap_or(0);
+    let mut best = None;
+    for presses in 0..=most {
+        button.iter().for_each(|&i| joltage[i] -= presses);
+        if let Some(more) = fewest_presses(rest, joltage) {
+            best = Some(best.map_or(presses + more, |best: u64| best.min(presses + more)));
+        }
+        button.iter().for_each(|&i| joltage[i] += presses);
+    }
+    best
+}
+
+pub fn part_1(input: &[u8]) -> u32 {
+    parse(input).iter().map(fewest_toggles).sum()
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    parse(input)
+        .into_iter()
+        .map(|mut machine| {
+            fewest_presses(&machine.buttons, &mut machine.joltage).expect("joltage can be reached")
+        })
+        .sum()
+}
@@ -1,51 +1,176 @@
+pub mod reference;
+
+use std::hash::Hash;
+
 use bstr::ByteSlice;
 use fnv::FnvHashMap;
+use itertools::Itertools;
+
+use crate::{
+    ParseError,
+    repl::{self, Explore},
+    solution::{Params, Part, Solution, Timed},
+};
 
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day11.txt");
 
-fn count_paths<'a>(
-    node: &'a [u8],
-    graph: &FnvHashMap<&'a [u8], Vec<&'a [u8]>>,
-    cache: &mut FnvHashMap<&'a [u8], u32>,
-) -> u32 {
-    if let Some(&cached) = cache.get(node) {
-        return cached;
+const DAY: u8 = 11;
+
+/// The devices each device sends to, by name.
+pub type Graph<'a> = FnvHashMap<&'a [u8], Vec<&'a [u8]>>;
+
+pub struct Input<'a> {
+    raw: &'a [u8],
+    pub graph: Graph<'a>,
+}
+
+impl<'a> Input<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        let graph = input
+            .lines()
+            .map(|line| {
+                let (device, targets) = line.split_once_str(": ").ok_or_else(|| {
+                    ParseError::new(DAY, input, line, "a `: ` after the device name")
+                })?;
+                let targets: Vec<&[u8]> = targets.split_str(" ").collect();
+                Ok((device, targets))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Input { raw: input, graph })
     }
+}
 
-    let mut total = 0;
-    if let Some(neighbors) = graph.get(node) {
-        for &neighbor in neighbors {
-            total += count_paths(neighbor, graph, cache);
-        }
+fn cycle(input: &[u8], node: &[u8]) -> ParseError {
+    ParseError::new(DAY, input, node, "a device not on a cycle")
+}
+
+fn too_many_paths(input: &[u8], node: &[u8], bits: u32) -> ParseError {
+    ParseError::new(
+        DAY,
+        input,
+        node,
+        format!("a device with fewer than 2^{bits} paths"),
+    )
+}
+
+/// A device on the way along some paths, along with whatever else decides
+/// how they are counted.
+trait Step<'a>: Copy + Eq + Hash {
+    type Paths: Copy + Default;
+
+    /// The bits [`Self::Paths`] has room for.
+    const BITS: u32;
+
+    fn device(self) -> &'a [u8];
+
+    /// The step to `output`, one of the devices this one sends to.
+    fn to(self, output: &'a [u8]) -> Self;
+
+    fn add(a: Self::Paths, b: Self::Paths) -> Option<Self::Paths>;
+}
+
+impl<'a> Step<'a> for &'a [u8] {
+    type Paths = u32;
+
+    const BITS: u32 = u32::BITS;
+
+    fn device(self) -> &'a [u8] {
+        self
+    }
+
+    fn to(self, output: &'a [u8]) -> Self {
+        output
     }
 
-    cache.insert(node, total);
-    total
+    fn add(a: u32, b: u32) -> Option<u32> {
+        a.checked_add(b)
+    }
 }
 
-pub fn part_1(input: &[u8]) -> u32 {
-    let graph = input
-        .lines()
-        .map(|line| {
-            let (input, targets) = line.split_once_str(": ").unwrap();
-            let targets: Vec<&[u8]> = targets.split_str(" ").collect();
-            (input, targets)
-        })
-        .collect::<FnvHashMap<_, _>>();
+/// The paths from `start` to the steps `cache` already has counts for.
+/// `cache` holds `None` for the steps still being counted, so reaching one of
+/// them again means the paths loop. The steps being counted are kept on a
+/// stack of their own rather than the call stack, which a long chain of
+/// devices would overflow.
+fn count_paths<'a, S: Step<'a>>(
+    input: &[u8],
+    start: S,
+    graph: &Graph<'a>,
+    cache: &mut FnvHashMap<S, Option<S::Paths>>,
+) -> Result<S::Paths, ParseError> {
+    match cache.get(&start) {
+        Some(&Some(cached)) => return Ok(cached),
+        Some(None) => return Err(cycle(input, start.device())),
+        None => {}
+    }
+    cache.insert(start, None);
+    // Each step being counted, how many of its outputs are done and the paths
+    // through those.
+    let mut stack = vec![(start, 0, S::Paths::default())];
 
-    let mut cache: FnvHashMap<&[u8], u32> = FnvHashMap::default();
-    cache.insert(b"out", 1);
+    while let Some((step, done, paths)) = stack.last_mut() {
+        let outputs = graph.get(step.device()).map_or(&[][..], Vec::as_slice);
+        let Some(&output) = outputs.get(*done) else {
+            let (step, _, paths) = stack.pop().unwrap();
+            cache.insert(step, Some(paths));
+            let Some((parent, _, total)) = stack.last_mut() else {
+                return Ok(paths);
+            };
+            *total = S::add(*total, paths)
+                .ok_or_else(|| too_many_paths(input, parent.device(), S::BITS))?;
+            continue;
+        };
+        *done += 1;
 
-    count_paths(b"you", &graph, &mut cache)
+        let next = step.to(output);
+        match cache.get(&next) {
+            Some(&Some(cached)) => {
+                *paths = S::add(*paths, cached)
+                    .ok_or_else(|| too_many_paths(input, step.device(), S::BITS))?;
+            }
+            Some(None) => return Err(cycle(input, output)),
+            None => {
+                cache.insert(next, None);
+                stack.push((next, 0, S::Paths::default()));
+            }
+        }
+    }
+    unreachable!("the start is only popped by returning its paths")
 }
 
-#[derive(Debug, Clone, Copy)]
+fn paths_between<'a>(input: &Input<'a>, from: &'a [u8], to: &'a [u8]) -> Result<u32, ParseError> {
+    let mut cache: FnvHashMap<&[u8], Option<u32>> = FnvHashMap::default();
+    cache.insert(to, Some(1));
+
+    count_paths(input.raw, from, &input.graph, &mut cache)
+}
+
+pub fn part_1(input: &Input) -> Result<u32, ParseError> {
+    paths_between(input, b"you", b"out")
+}
+
+#[derive(Debug, Clone, Copy, Default)]
 struct CacheEntryPart2 {
     contains_only_fft: u64,
     contains_only_dac: u64,
     contains_both: u64,
 }
 
+impl CacheEntryPart2 {
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Some(CacheEntryPart2 {
+            contains_only_fft: self
+                .contains_only_fft
+                .checked_add(other.contains_only_fft)?,
+            contains_only_dac: self
+                .contains_only_dac
+                .checked_add(other.contains_only_dac)?,
+            contains_both: self.contains_both.checked_add(other.contains_both)?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct StatePart2<'a> {
     name: &'a [u8],
@@ -53,124 +178,153 @@ struct StatePart2<'a> {
     dac_included: bool,
 }
 
-fn count_paths_part2<'a>(
-    state: StatePart2<'a>,
-    graph: &FnvHashMap<&'a [u8], Vec<&'a [u8]>>,
-    cache: &mut FnvHashMap<StatePart2<'a>, CacheEntryPart2>,
-) -> CacheEntryPart2 {
-    if let Some(&cached) = cache.get(&state) {
-        return cached;
-    }
+impl<'a> Step<'a> for StatePart2<'a> {
+    type Paths = CacheEntryPart2;
 
-    if state.name == b"out" {
-        let entry = CacheEntryPart2 {
-            contains_only_fft: if state.fft_included && !state.dac_included {
-                1
-            } else {
-                0
-            },
-            contains_only_dac: if state.dac_included && !state.fft_included {
-                1
-            } else {
-                0
-            },
-            contains_both: if state.fft_included && state.dac_included {
-                1
-            } else {
-                0
-            },
-        };
-        cache.insert(state, entry);
-        return entry;
-    }
+    const BITS: u32 = u64::BITS;
 
-    let mut entry = CacheEntryPart2 {
-        contains_only_fft: 0,
-        contains_only_dac: 0,
-        contains_both: 0,
-    };
+    fn device(self) -> &'a [u8] {
+        self.name
+    }
 
-    if let Some(neighbors) = graph.get(state.name) {
-        for &neighbor in neighbors {
-            let neighbor_state = StatePart2 {
-                name: neighbor,
-                fft_included: state.fft_included || neighbor == b"fft",
-                dac_included: state.dac_included || neighbor == b"dac",
-            };
-            let neighbor_entry = count_paths_part2(neighbor_state, graph, cache);
-            entry.contains_only_fft += neighbor_entry.contains_only_fft;
-            entry.contains_only_dac += neighbor_entry.contains_only_dac;
-            entry.contains_both += neighbor_entry.contains_both;
+    fn to(self, output: &'a [u8]) -> Self {
+        StatePart2 {
+            name: output,
+            fft_included: self.fft_included || output == b"fft",
+            dac_included: self.dac_included || output == b"dac",
         }
     }
-    cache.insert(state, entry);
-    entry
-}
 
-pub fn part_2(input: &[u8]) -> u64 {
-    let graph = input
-        .lines()
-        .map(|line| {
-            let (input, targets) = line.split_once_str(": ").unwrap();
-            let targets: Vec<&[u8]> = targets.split_str(" ").collect();
-            (input, targets)
-        })
-        .collect::<FnvHashMap<_, _>>();
+    fn add(a: CacheEntryPart2, b: CacheEntryPart2) -> Option<CacheEntryPart2> {
+        a.checked_add(b)
+    }
+}
 
-    let mut cache: FnvHashMap<StatePart2, CacheEntryPart2> = FnvHashMap::default();
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    let mut cache: FnvHashMap<StatePart2, Option<CacheEntryPart2>> = FnvHashMap::default();
+    // The paths end at `out`, counted by which of `fft` and `dac` they passed.
+    for (fft_included, dac_included) in [(false, false), (true, false), (false, true), (true, true)]
+    {
+        let end = StatePart2 {
+            name: b"out",
+            fft_included,
+            dac_included,
+        };
+        let entry = CacheEntryPart2 {
+            contains_only_fft: u64::from(fft_included && !dac_included),
+            contains_only_dac: u64::from(dac_included && !fft_included),
+            contains_both: u64::from(fft_included && dac_included),
+        };
+        cache.insert(end, Some(entry));
+    }
 
     let start = StatePart2 {
         name: b"svr",
         fft_included: false,
         dac_included: false,
     };
-    let result = count_paths_part2(start, &graph, &mut cache);
-    result.contains_both
+    let result = count_paths(input.raw, start, &input.graph, &mut cache)?;
+    Ok(result.contains_both)
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
-    println!("Part 2: {}", part_2(INPUT));
+pub fn try_part_1(input: &[u8]) -> Result<u32, ParseError> {
+    part_1(&Input::parse(input)?)
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    part_2(&Input::parse(input)?)
+}
+
+impl Explore for Input<'_> {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            (
+                "paths FROM TO",
+                "how many paths lead from device FROM to TO",
+            ),
+            ("outputs DEVICE", "the devices DEVICE sends to"),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        Some(match command {
+            "paths" => repl::arg::<String>(args, 0, "FROM").and_then(|from| {
+                let to = repl::arg::<String>(args, 1, "TO")?;
+                paths_between(self, from.as_bytes(), to.as_bytes())
+                    .map(|paths| paths.to_string())
+                    .map_err(|e| e.to_string())
+            }),
+            "outputs" => repl::arg::<String>(args, 0, "DEVICE").and_then(|device| {
+                let outputs = self
+                    .graph
+                    .get(device.as_bytes())
+                    .ok_or_else(|| format!("no device `{device}` has outputs"))?;
+                Ok(outputs.iter().map(|output| output.as_bstr()).join(" "))
+            }),
+            _ => return None,
+        })
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Reactor"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, part_1),
+            Part::Two => Timed::measure(input, Input::parse, part_2),
+        })
+    }
+
+    fn explore<'a>(&self, input: &'a [u8]) -> Option<Result<Box<dyn Explore + 'a>, ParseError>> {
+        Some(Input::parse(input).map(|input| Box::new(input) as Box<dyn Explore>))
+    }
+}
+
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!(
+        "Part 1: {}",
+        part_1(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
+    println!(
+        "Part 2: {}",
+        part_2(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_1: &[u8] = b"aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out";
-
-    const EXAMPLE_2: &[u8] = b"svr: aaa bbb
-aaa: fft
-fft: ccc
-bbb: tty
-tty: ccc
-ccc: ddd eee
-ddd: hub
-hub: fff
-eee: dac
-dac: fff
-fff: ggg hhh
-ggg: out
-hhh: out";
+    crate::example_tests!(DAY);
+
+    #[test]
+    fn test_parse_error() {
+        let err = try_part_2(b"svr: aaa\naaa out").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
 
     #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE_1);
-        assert_eq!(result, 5);
+    fn test_cycle() {
+        let err = try_part_1(b"you: aaa\naaa: bbb out\nbbb: aaa").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 6));
+        assert!(try_part_2(b"svr: fft\nfft: dac\ndac: svr out").is_err());
     }
 
     #[test]
-    fn test_part_2() {
-        let result = part_2(EXAMPLE_2);
-        assert_eq!(result, 2);
+    fn test_long_chain() {
+        let chain = (0..100_000).map(|i| format!("d{i}: d{}", i + 1)).join("\n");
+        let input = format!("you: d0\nsvr: fft\nfft: dac\ndac: d0\n{chain}\nd100000: out");
+        assert_eq!(try_part_1(input.as_bytes()).unwrap(), 1);
+        assert_eq!(try_part_2(input.as_bytes()).unwrap(), 1);
     }
 }
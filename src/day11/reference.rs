@@ -0,0 +1,48 @@
+//! Walks every path through the devices one by one.
+
+use std::collections::HashMap;
+
+fn parse(input: &[u8]) -> HashMap<&str, Vec<&str>> {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    input
+        .lines()
+        .map(|line| {
+            let (device, outputs) = line.split_once(": ").unwrap();
+            (device, outputs.split(' ').collect())
+        })
+        .collect()
+}
+
+/// Calls `visit` with every path from `device` to `out`.
+fn walk<'a>(
+    devices: &HashMap<&'a str, Vec<&'a str>>,
+    device: &'a str,
+    path: &mut Vec<&'a str>,
+    visit: &mut impl FnMut(&[&'a str]),
+) {
+    path.push(device);
+    if device == "out" {
+        visit(path);
+    } else {
+        for &next in devices.get(device).into_iter().flatten() {
+            walk(devices, next, path, visit);
+        }
+    }
+    path.pop();
+}
+
+pub fn part_1(input: &[u8]) -> u32 {
+    let mut paths = 0;
+    walk(&parse(input), "you", &mut Vec::new(), &mut |_| paths += 1);
+    paths
+}
+
+pub fn part_2(input: &[u8]) -> u64 {
+    let mut paths = 0;
+    walk(&parse(input), "svr", &mut Vec::new(), &mut |path| {
+        if path.contains(&"dac") && path.contains(&"fft") {
+            paths += 1;
+        }
+    });
+    paths
+}
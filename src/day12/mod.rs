@@ -1,74 +1,132 @@
+pub mod reference;
+
 use bstr::ByteSlice;
 
+use crate::{
+    ParseError, parse,
+    solution::{Params, Part, Solution, Timed},
+};
+
+#[cfg(feature = "embedded-inputs")]
 pub const INPUT: &[u8] = include_bytes!("../../inputs/day12.txt");
 
-fn parse_uint(s: &[u8]) -> u16 {
-    s.iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as u16)
+const DAY: u8 = 12;
+
+pub struct Region {
+    pub width: u32,
+    pub height: u32,
+    /// How many presents of each shape must fit.
+    pub shape_counts: Vec<u64>,
 }
 
-pub fn part_1(input: &[u8]) -> usize {
-    let (_shapes_raw, regions_raw) = input.rsplit_once_str("\n\n").unwrap();
+/// The regions under the trees. The shapes are not kept, since every
+/// present is counted as the same size.
+pub struct Input {
+    pub regions: Vec<Region>,
+}
 
-    regions_raw
-        .lines()
-        .filter(|region_block| {
-            let (dimensions_raw, shape_counts_raw) = region_block.split_once_str(": ").unwrap();
-            let (w, h) = dimensions_raw
-                .split_once_str("x")
-                .map(|(w, h)| (parse_uint(w), parse_uint(h)))
-                .unwrap();
-            let shape_count = shape_counts_raw.split_str(" ").map(parse_uint).sum::<u16>();
+impl Input {
+    pub fn parse(input: &[u8]) -> Result<Self, ParseError> {
+        let (_shapes_raw, regions_raw) = input.rsplit_once_str("\n\n").ok_or_else(|| {
+            ParseError::new(
+                DAY,
+                input,
+                &input[input.len()..],
+                "a blank line before the regions",
+            )
+        })?;
 
-            w * h * 2 >= 17 * shape_count
+        let regions = regions_raw
+            .lines()
+            .map(|region_block| {
+                let (dimensions_raw, shape_counts_raw) = region_block
+                    .split_once_str(": ")
+                    .ok_or_else(|| ParseError::new(DAY, input, region_block, "a `WxH: ` region"))?;
+                let (w, h) = dimensions_raw.split_once_str("x").ok_or_else(|| {
+                    ParseError::new(DAY, input, dimensions_raw, "`WxH` dimensions")
+                })?;
+                let width = parse::expect_uint(DAY, input, w, "a 32-bit width")?;
+                let height = parse::expect_uint(DAY, input, h, "a 32-bit height")?;
+                let shape_counts = shape_counts_raw
+                    .split_str(" ")
+                    .map(|count| parse::expect_uint::<u64>(DAY, input, count, "a shape count"))
+                    .collect::<Result<_, _>>()?;
+                Ok(Region {
+                    width,
+                    height,
+                    shape_counts,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Input { regions })
+    }
+}
+
+pub fn part_1(input: &Input) -> usize {
+    input
+        .regions
+        .iter()
+        .filter(|region| {
+            // `u128` leaves room for the area and any number of 64-bit counts.
+            let shape_count = region
+                .shape_counts
+                .iter()
+                .map(|&count| u128::from(count))
+                .sum::<u128>();
+            u128::from(region.width) * u128::from(region.height) * 2 >= 17 * shape_count
         })
         .count()
 }
 
-pub fn run() {
-    println!("Part 1: {}", part_1(INPUT));
+pub fn try_part_1(input: &[u8]) -> Result<usize, ParseError> {
+    Input::parse(input).map(|input| part_1(&input))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn day(&self) -> u8 {
+        DAY
+    }
 
-    const EXAMPLE: &[u8] = b"0:
-###
-##.
-##.
+    fn title(&self) -> &'static str {
+        "Christmas Tree Farm"
+    }
 
-1:
-###
-##.
-.##
+    fn parts(&self) -> &'static [Part] {
+        &[Part::One]
+    }
 
-2:
-.##
-###
-##.
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        match part {
+            Part::One => Some(Timed::measure(input, Input::parse, |input| {
+                Ok(part_1(input))
+            })),
+            Part::Two => None,
+        }
+    }
+}
 
-3:
-##.
-###
-##.
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!("Part 1: {}", part_1(&input));
+}
 
-4:
-###
-#..
-###
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-5:
-###
-.#.
-###
+    crate::example_tests!(DAY, test_part_1 => One);
 
-4x4: 0 0 0 0 2 0
-12x5: 1 0 1 0 2 2
-12x5: 1 0 1 0 3 2";
+    #[test]
+    fn test_parse_error() {
+        let err = try_part_1(b"0:\n###\n\n4x4: 0 a").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 8));
+    }
 
     #[test]
-    fn test_part_1() {
-        let result = part_1(EXAMPLE);
-        assert_eq!(result, 2);
+    fn test_large_region() {
+        let input = b"0:\n###\n\n300x300: 5000 5000\n";
+        assert_eq!(try_part_1(input).unwrap(), 1);
     }
 }
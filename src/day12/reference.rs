@@ -0,0 +1,159 @@
+//! Tries every position and orientation of every present in turn, for regions
+//! small enough to search.
+
+use std::collections::BTreeSet;
+
+/// Width, height and how many of each present go in it.
+type Region = (usize, usize, Vec<usize>);
+
+fn parse(input: &[u8]) -> (Vec<Vec<(usize, usize)>>, Vec<Region>) {
+    let input = std::str::from_utf8(input).expect("input is ASCII");
+    let (shapes, regions) = input.rsplit_once("\n\n").unwrap();
+    let shapes = shapes
+        .split("\n\n")
+        .map(|shape| {
+            shape
+                .lines()
+                .skip(1)
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.char_indices()
+                        .filter(|&(_, c)| c == '#')
+                        .map(move |(x, _)| (x, y))
+                })
+                .collect()
+        })
+        .collect();
+    let regions = regions
+        .lines()
+        .map(|line| {
+            let (size, counts) = line.split_once(": ").unwrap();
+            let (w, h) = size.split_once('x').unwrap();
+            let counts = counts.split(' ').map(|c| c.parse().unwrap()).collect();
+            (w.parse().unwrap(), h.parse().unwrap(), counts)
+        })
+        .collect();
+    (shapes, regions)
+}
+
+/// The shape rotated a quarter turn, moved back to the top left.
+fn rotate(tiles: &BTreeSet<(usize, usize)>) -> BTreeSet<(usize, usize)> {
+    let height = tiles.iter().map(|&(_, y)| y).max().unwrap();
+    tiles.iter().map(|&(x, y)| (height - y, x)).collect()
+}
+
+fn mirror(tiles: &BTreeSet<(usize, usize)>) -> BTreeSet<(usize, usize)> {
+    let width = tiles.iter().map(|&(x, _)| x).max().unwrap();
+    tiles.iter().map(|&(x, y)| (width - x, y)).collect()
+}
+
+/// The tiles each orientation of `shape` covers at each position in a
+/// `w`x`h` region.
+fn placements(shape: &[(usize, usize)], w: usize, h: usize) -> Vec<Vec<usize>> {
+    let min_x = shape.iter().map(|&(x, _)| x).min().unwrap();
+    let min_y = shape.iter().map(|&(_, y)| y).min().unwrap();
+    let mut orientation = shape
+        .iter()
+        .map(|&(x, y)| (x - min_x, y - min_y))
+        .collect::<BTreeSet<_>>();
+    let mut orientations = BTreeSet::new();
+    for _ in 0..4 {
+        orientations.insert(mirror(&orientation));
+        orientations.insert(orientation.clone());
+        orientation = rotate(&orientation);
+    }
+
+    let mut placements = Vec::new();
+    for orientation in orientations {
+        for top in 0..h {
+            for left in 0..w {
+                if orientation
+                    .iter()
+                    .all(|&(x, y)| left + x < w && top + y < h)
+                {
+                    placements.push(
+                        orientation
+                            .iter()
+                            .map(|&(x, y)| (top + y) * w + left + x)
+                            .collect(),
+                    );
+                }
+            }
+        }
+    }
+    placements
+}
+
+/// Whether `presents[i..]` fit among the tiles not yet `taken`. A present
+/// the same as the one before it only tries the placements after that one's,
+/// so that swapping the two is not tried again.
+fn fits(
+    presents: &[usize],
+    placements: &[Vec<Vec<usize>>],
+    i: usize,
+    first: usize,
+    taken: &mut [bool],
+) -> bool {
+    let Some(&shape) = presents.get(i) else {
+        return true;
+    };
+    // The presents left need more tiles than their free placements cover.
+    let tiles_left = presents[i..]
+        .iter()
+        .map(|&shape| placements[shape].first().map_or(usize::MAX, Vec::len))
+        .fold(0, usize::saturating_add);
+    let mut coverable = vec![false; taken.len()];
+    for shape in presents[i..].iter().copied().collect::<BTreeSet<_>>() {
+        for placement in &placements[shape] {
+            if placement.iter().all(|&tile| !taken[tile]) {
+                placement.iter().for_each(|&tile| coverable[tile] = true);
+            }
+        }
+    }
+    if tiles_left > coverable.iter().filter(|&&coverable| coverable).count() {
+        return false;
+    }
+    for (j, placement) in placements[shape].iter().enumerate().skip(first) {
+        if placement.iter().any(|&tile| taken[tile]) {
+            continue;
+        }
+        placement.iter().for_each(|&tile| taken[tile] = true);
+        let next_first = if presents.get(i + 1) == Some(&shape) {
+            j + 1
+        } else {
+            0
+        };
+        let found = fits(presents, placements, i + 1, next_first, taken);
+        placement.iter().for_each(|&tile| taken[tile] = false);
+        if found {
+            return true;
+        }
+    }
+    false
+}
+
+/// # Panics
+///
+/// On a region with more than 144 tiles, which would take too long.
+pub fn part_1(input: &[u8]) -> usize {
+    let (shapes, regions) = parse(input);
+    regions
+        .into_iter()
+        .filter(|(w, h, counts)| {
+            assert!(w * h <= 144, "{w}x{h} is too large to search");
+            let placements = shapes
+                .iter()
+                .map(|shape| placements(shape, *w, *h))
+                .collect::<Vec<_>>();
+            // The largest presents go first, copies of a shape next to each
+            // other.
+            let mut presents = counts
+                .iter()
+                .enumerate()
+                .flat_map(|(shape, &count)| std::iter::repeat_n(shape, count))
+                .collect::<Vec<_>>();
+            presents.sort_by_key(|&shape| (std::cmp::Reverse(shapes[shape].len()), shape));
+            fits(&presents, &placements, 0, 0, &mut vec![false; w * h])
+        })
+        .count()
+}
@@ -0,0 +1,97 @@
+use std::{borrow::Cow, fmt};
+
+use bstr::ByteSlice;
+
+/// Malformed puzzle input, located by line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based byte column within the line.
+    pub column: usize,
+    pub expected: Cow<'static, str>,
+    /// The offending token, or `None` at the end of a line or the input.
+    pub found: Option<String>,
+    source_line: String,
+}
+
+impl ParseError {
+    /// Builds an error pointing at `at`, which must be a subslice of `input`.
+    pub fn new(day: u8, input: &[u8], at: &[u8], expected: impl Into<Cow<'static, str>>) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .wrapping_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let line_start = input[..offset].rfind_byte(b'\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find_byte(b'\n')
+            .map_or(input.len(), |i| offset + i);
+        let token_end = line_end.min(offset + at.len());
+
+        ParseError {
+            day,
+            line: input[..line_start].find_iter(b"\n").count() + 1,
+            column: offset - line_start + 1,
+            expected: expected.into(),
+            found: (token_end > offset)
+                .then(|| input[offset..token_end].to_str_lossy().into_owned()),
+            source_line: input[line_start..line_end].to_str_lossy().into_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02}, line {}, column {}: expected {}, found ",
+            self.day, self.line, self.column, self.expected
+        )?;
+        match &self.found {
+            Some(found) => writeln!(f, "{found:?}")?,
+            None if self.column > self.source_line.len() => writeln!(f, "end of line")?,
+            None => writeln!(f, "nothing")?,
+        }
+
+        let gutter = self.line.to_string().len();
+        let width = self.found.as_ref().map_or(1, |found| found.len().max(1));
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{:gutter$} | {:pad$}{}",
+            "",
+            "",
+            "^".repeat(width),
+            pad = self.column - 1
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let input = b"L68\nL30\nX48\nL5";
+        let err = ParseError::new(1, input, &input[8..11], "`L` or `R`");
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.found.as_deref(), Some("X48"));
+        assert_eq!(
+            err.to_string(),
+            "day 01, line 3, column 1: expected `L` or `R`, found \"X48\"\n  |\n3 | X48\n  | ^^^"
+        );
+    }
+
+    #[test]
+    fn test_end_of_input() {
+        let input = b"3-5\n10-14";
+        let err = ParseError::new(5, input, &input[input.len()..], "a blank line");
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.found, None);
+        assert!(err.to_string().contains("found end of line"));
+    }
+}
@@ -0,0 +1,276 @@
+//! The puzzle examples in `examples/dayNN/`, with their expected answers and
+//! parameters in `examples/manifest.toml`:
+//!
+//! ```toml
+//! ["day08/example.txt"]
+//! part1 = 40
+//! part2 = 25272
+//! params = { steps = 10 }
+//! ```
+//!
+//! Each day's tests come from [`example_tests!`](crate::example_tests).
+
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{Answer, ParseError, Part, Solution, solution::Params};
+
+/// Location of the example files and their manifest.
+pub fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    /// Location relative to [`examples_dir`], such as `day11/example-2.txt`.
+    pub file: String,
+    expected: BTreeMap<Part, String>,
+    pub params: Params,
+}
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Invalid { key: String, reason: &'static str },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::Io(e) => e.fmt(f),
+            ManifestError::Toml(e) => e.fmt(f),
+            ManifestError::Invalid { key, reason } => write!(f, "`{key}`: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    part1: Option<toml::Value>,
+    part2: Option<toml::Value>,
+    #[serde(default)]
+    params: Params,
+}
+
+/// Parses a manifest, sorting the examples by file.
+pub fn parse(s: &str) -> Result<Vec<Example>, ManifestError> {
+    let table: BTreeMap<String, Entry> = toml::from_str(s).map_err(ManifestError::Toml)?;
+
+    let mut examples = Vec::new();
+    for (file, entry) in table {
+        let day = file
+            .strip_prefix("day")
+            .and_then(|rest| rest.split_once('/'))
+            .and_then(|(day, _)| day.parse().ok())
+            .ok_or_else(|| ManifestError::Invalid {
+                key: file.clone(),
+                reason: "expected a file in a `dayNN/` directory",
+            })?;
+
+        let mut expected = BTreeMap::new();
+        for (part, value) in [(Part::One, entry.part1), (Part::Two, entry.part2)] {
+            let answer = match value {
+                None => continue,
+                Some(toml::Value::Integer(n)) => n.to_string(),
+                Some(toml::Value::String(s)) => s,
+                Some(_) => {
+                    return Err(ManifestError::Invalid {
+                        key: format!("{file}.part{part}"),
+                        reason: "expected an integer or a string",
+                    });
+                }
+            };
+            expected.insert(part, answer);
+        }
+
+        examples.push(Example {
+            day,
+            file,
+            expected,
+            params: entry.params,
+        });
+    }
+    Ok(examples)
+}
+
+/// Reads the manifest in [`examples_dir`].
+pub fn load() -> Result<Vec<Example>, ManifestError> {
+    let manifest =
+        std::fs::read_to_string(examples_dir().join("manifest.toml")).map_err(ManifestError::Io)?;
+    parse(&manifest)
+}
+
+/// The examples for `day`, in file order.
+pub fn for_day(day: u8) -> Result<Vec<Example>, ManifestError> {
+    let mut examples = load()?;
+    examples.retain(|example| example.day == day);
+    Ok(examples)
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        examples_dir().join(&self.file)
+    }
+
+    pub fn read(&self) -> io::Result<Vec<u8>> {
+        std::fs::read(self.path())
+    }
+
+    /// The expected answer for `part`, if the example has one.
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        self.expected.get(&part).map(String::as_str)
+    }
+
+    /// Solves `part` of `input` with the example's parameters.
+    pub fn solve(
+        &self,
+        solution: &dyn Solution,
+        part: Part,
+        input: &[u8],
+    ) -> Option<Result<Answer, ParseError>> {
+        solution.solve_with(part, input, &self.params)
+    }
+}
+
+/// Checks `part` of `day` against every example that has an answer for it.
+///
+/// # Panics
+///
+/// On any wrong answer or parse error, or if there is no such example.
+#[doc(hidden)]
+pub fn check(day: u8, part: Part) {
+    let solution = crate::solution(day).unwrap_or_else(|e| panic!("{e}"));
+    let examples = for_day(day).unwrap_or_else(|e| panic!("examples/manifest.toml: {e}"));
+
+    let mut checked = 0;
+    for example in &examples {
+        let Some(expected) = example.expected(part) else {
+            continue;
+        };
+        let input = example
+            .read()
+            .unwrap_or_else(|e| panic!("{}: {e}", example.file));
+        let answer = example
+            .solve(solution, part, &input)
+            .unwrap_or_else(|| panic!("day {day} has no part {part}"))
+            .unwrap_or_else(|e| panic!("{}: {e}", example.file));
+        assert_eq!(answer.to_string(), expected, "{}", example.file);
+        checked += 1;
+    }
+    assert!(checked > 0, "no example answers day {day} part {part}");
+}
+
+/// Defines a test per part that checks the day's examples, named
+/// `test_part_1` and `test_part_2` unless given, as in
+/// `example_tests!(DAY, test_part_1 => One)` for a day with only part 1.
+#[macro_export]
+macro_rules! example_tests {
+    ($day:expr) => {
+        $crate::example_tests!($day, test_part_1 => One, test_part_2 => Two);
+    };
+    ($day:expr, $($name:ident => $part:ident),+ $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                $crate::examples::check($day, $crate::Part::$part);
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let examples = parse(
+            r#"
+["day08/example.txt"]
+part1 = 40
+part2 = "25272"
+params = { steps = 10 }
+
+["day11/example-2.txt"]
+part2 = 2
+"#,
+        )
+        .unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].day, 8);
+        assert_eq!(examples[0].expected(Part::Two), Some("25272"));
+        assert_eq!(examples[0].params["steps"], 10);
+        assert_eq!(examples[1].expected(Part::One), None);
+
+        assert!(matches!(
+            parse("[\"example.txt\"]\npart1 = 1"),
+            Err(ManifestError::Invalid { .. })
+        ));
+        assert!(matches!(
+            parse("[\"day01/example.txt\"]\npart3 = 1"),
+            Err(ManifestError::Toml(_))
+        ));
+    }
+
+    #[test]
+    fn test_manifest_matches_files() {
+        let mut listed = load()
+            .unwrap()
+            .into_iter()
+            .map(|example| example.file)
+            .collect::<Vec<_>>();
+        listed.sort();
+
+        let mut files = Vec::new();
+        for dir in examples_dir().read_dir().unwrap() {
+            let dir = dir.unwrap();
+            if !dir.file_type().unwrap().is_dir() {
+                continue;
+            }
+            for file in dir.path().read_dir().unwrap() {
+                let file = file.unwrap().path();
+                let file = file.strip_prefix(examples_dir()).unwrap();
+                files.push(file.to_str().unwrap().to_string());
+            }
+        }
+        files.sort();
+
+        assert_eq!(listed, files);
+    }
+
+    #[test]
+    fn test_every_day_has_examples() {
+        let examples = load().unwrap();
+        for solution in crate::SOLUTIONS {
+            let examples = examples
+                .iter()
+                .filter(|e| e.day == solution.day())
+                .collect::<Vec<_>>();
+            assert!(!examples.is_empty(), "day {}", solution.day());
+            // A day just added has no answers until it is solved.
+            if examples
+                .iter()
+                .all(|e| Part::BOTH.iter().all(|&part| e.expected(part).is_none()))
+            {
+                continue;
+            }
+            for &part in solution.parts() {
+                assert!(
+                    examples.iter().any(|e| e.expected(part).is_some()),
+                    "day {} part {part}",
+                    solution.day()
+                );
+            }
+        }
+    }
+}
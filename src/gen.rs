@@ -0,0 +1,558 @@
+//! Random puzzle inputs in the shape of the real ones, for scaling
+//! benchmarks and reproducible bug reports.
+//!
+//! The same day, seed and size always produce the same input.
+
+use std::{fmt::Write, ops::RangeInclusive};
+
+use fnv::FnvHashSet;
+use itertools::Itertools;
+
+use crate::UnknownDay;
+
+/// SplitMix64, which is plenty for test data and keeps the inputs stable
+/// across dependency updates.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, without noticeable bias for the small ranges used
+    /// here.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+        match (end - start).checked_add(1) {
+            Some(len) => start + ((u128::from(self.next_u64()) * u128::from(len)) >> 64) as u64,
+            None => self.next_u64(),
+        }
+    }
+
+    /// An index below `len`.
+    pub fn below(&mut self, len: usize) -> usize {
+        assert!(len > 0, "no index below 0");
+        self.range(0..=len as u64 - 1) as usize
+    }
+
+    /// `true` with a chance of `percent` in 100.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.range(0..=99) < percent
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+struct Generator {
+    day: u8,
+    /// Roughly the size of the real inputs.
+    default_size: usize,
+    /// The largest size worth benchmarking. Some days' solutions grow faster
+    /// than their input and take minutes on much larger ones.
+    max_size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+static GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        default_size: 4_000,
+        max_size: 64_000,
+        generate: day01,
+    },
+    Generator {
+        day: 2,
+        default_size: 30,
+        max_size: 480,
+        generate: day02,
+    },
+    Generator {
+        day: 3,
+        default_size: 200,
+        max_size: 3_200,
+        generate: day03,
+    },
+    Generator {
+        day: 4,
+        default_size: 135,
+        max_size: 2_160,
+        generate: day04,
+    },
+    Generator {
+        day: 5,
+        default_size: 180,
+        max_size: 2_880,
+        generate: day05,
+    },
+    Generator {
+        day: 6,
+        default_size: 1_000,
+        max_size: 16_000,
+        generate: day06,
+    },
+    Generator {
+        day: 7,
+        default_size: 141,
+        max_size: 2_256,
+        generate: day07,
+    },
+    Generator {
+        day: 8,
+        default_size: 1_000,
+        max_size: 1_000,
+        generate: day08,
+    },
+    Generator {
+        day: 9,
+        default_size: 500,
+        max_size: 500,
+        generate: day09,
+    },
+    Generator {
+        day: 10,
+        default_size: 170,
+        max_size: 680,
+        generate: day10,
+    },
+    Generator {
+        day: 11,
+        default_size: 600,
+        max_size: 9_600,
+        generate: day11,
+    },
+    Generator {
+        day: 12,
+        default_size: 1_000,
+        max_size: 16_000,
+        generate: day12,
+    },
+];
+
+fn generator(day: u8) -> Result<&'static Generator, UnknownDay> {
+    GENERATORS
+        .iter()
+        .find(|generator| generator.day == day)
+        .ok_or(UnknownDay(day))
+}
+
+/// The size that makes [`generate`] produce an input about as large as the
+/// real one for `day`.
+pub fn default_size(day: u8) -> Result<usize, UnknownDay> {
+    generator(day).map(|generator| generator.default_size)
+}
+
+/// The largest size worth benchmarking `day` at, no less than its
+/// [`default_size`].
+pub fn max_size(day: u8) -> Result<usize, UnknownDay> {
+    generator(day).map(|generator| generator.max_size)
+}
+
+/// A valid input for `day`. `size` is the number of records in it: lines,
+/// ranges, machines or devices, or the side length for the grid days.
+pub fn generate(day: u8, seed: u64, size: usize) -> Result<String, UnknownDay> {
+    let generator = generator(day)?;
+    Ok((generator.generate)(&mut Rng::new(seed), size.max(1)))
+}
+
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+fn day01(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let direction = if rng.chance(50) { 'L' } else { 'R' };
+        format!("{direction}{}", rng.range(1..=999))
+    }))
+}
+
+fn day02(rng: &mut Rng, size: usize) -> String {
+    // Every ID in a range gets checked, so the ranges stay short.
+    let ranges = (0..size)
+        .map(|_| {
+            let digits = rng.range(1..=10) as u32;
+            let start = rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+            let end = start + rng.range(0..=start.min(100_000));
+            format!("{start}-{end}")
+        })
+        .join(",");
+    ranges + "\n"
+}
+
+fn day03(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| (0..100).map(|_| rng.range(1..=9).to_string()).collect()))
+}
+
+fn day04(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        (0..size)
+            .map(|_| if rng.chance(60) { '@' } else { '.' })
+            .collect()
+    }))
+}
+
+fn day05(rng: &mut Rng, size: usize) -> String {
+    const MAX_ID: u64 = 500_000_000_000_000;
+    let ranges = (0..size)
+        .map(|_| {
+            let start = rng.range(1..=MAX_ID);
+            (start, start + rng.range(0..=MAX_ID / 100))
+        })
+        .collect_vec();
+    // Half the IDs fall inside some range, so both answers depend on them.
+    let ids = (0..size * 5).map(|_| {
+        if rng.chance(50) {
+            let (start, end) = ranges[rng.below(ranges.len())];
+            rng.range(start..=end)
+        } else {
+            rng.range(1..=MAX_ID)
+        }
+    });
+    format!(
+        "{}\n{}",
+        lines(ranges.iter().map(|(start, end)| format!("{start}-{end}"))),
+        lines(ids.map(|id| id.to_string()))
+    )
+}
+
+fn day06(rng: &mut Rng, size: usize) -> String {
+    const ROWS: usize = 4;
+    let problems = (0..size)
+        .map(|_| {
+            let numbers = (0..ROWS)
+                .map(|_| {
+                    let digits = rng.range(1..=4) as u32;
+                    let n = rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+                    (n.to_string(), rng.chance(50))
+                })
+                .collect_vec();
+            let width = numbers.iter().map(|(n, _)| n.len()).max().unwrap();
+            (rng.chance(50), width, numbers)
+        })
+        .collect_vec();
+
+    let mut sheet = (0..ROWS)
+        .map(|row| {
+            problems
+                .iter()
+                .map(|(_, width, numbers)| match &numbers[row] {
+                    (n, true) => format!("{n:<width$}"),
+                    (n, false) => format!("{n:>width$}"),
+                })
+                .join(" ")
+        })
+        .collect_vec();
+    sheet.push(
+        problems
+            .iter()
+            .map(|&(multiply, width, _)| format!("{:<width$}", if multiply { '*' } else { '+' }))
+            .join(" "),
+    );
+    lines(sheet)
+}
+
+fn day07(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(3) | 1;
+    // Every row of splitters at most doubles the timelines, and 63 of them
+    // still fit in a `u64`.
+    let step = size.div_ceil(63).max(2);
+    let first: String = (0..width)
+        .map(|x| if x == width / 2 { 'S' } else { '.' })
+        .collect();
+    let rows = (1..size).map(|y| {
+        (0..width)
+            .map(|x| {
+                let inside = x > 0 && x < width - 1;
+                if inside && y % step == 0 && rng.chance(40) {
+                    '^'
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    });
+    lines(std::iter::once(first).chain(rows))
+}
+
+fn day08(rng: &mut Rng, size: usize) -> String {
+    let mut seen = FnvHashSet::default();
+    let boxes = std::iter::repeat_with(|| {
+        (
+            rng.range(0..=99_999),
+            rng.range(0..=99_999),
+            rng.range(0..=99_999),
+        )
+    })
+    .filter(|&pos| seen.insert(pos))
+    .take(size.max(2));
+    lines(boxes.map(|(x, y, z)| format!("{x},{y},{z}")))
+}
+
+fn day09(rng: &mut Rng, size: usize) -> String {
+    // Columns of floor that all span the middle row, walked along the top
+    // and back along the bottom. Neighbouring columns never share a top or
+    // bottom edge row, nor use adjacent ones, which `day09` needs to see the
+    // loop as the puzzle means it.
+    const HEIGHT: u64 = 99_999;
+    let columns = (size / 4).max(1);
+    let max_width = (2 * HEIGHT / columns as u64).max(2);
+
+    let mut x = 0;
+    let (mut top, mut bottom) = (Vec::new(), Vec::new());
+    let (mut last_bottom, mut last_top) = (u64::MAX, u64::MAX);
+    for _ in 0..columns {
+        let (bottom_y, top_y) = loop {
+            let bottom_y = rng.range(0..=HEIGHT / 2 - 1);
+            let top_y = rng.range(HEIGHT / 2 + 1..=HEIGHT);
+            if bottom_y.abs_diff(last_bottom) >= 2 && top_y.abs_diff(last_top) >= 2 {
+                break (bottom_y, top_y);
+            }
+        };
+        let width = rng.range(2..=max_width);
+        top.extend([(x, top_y), (x + width, top_y)]);
+        bottom.extend([(x, bottom_y), (x + width, bottom_y)]);
+        (last_bottom, last_top) = (bottom_y, top_y);
+        x += width;
+    }
+    bottom.reverse();
+    lines(
+        top.into_iter()
+            .chain(bottom)
+            .map(|(x, y)| format!("{x},{y}")),
+    )
+}
+
+fn day10(rng: &mut Rng, size: usize) -> String {
+    // The target lights and joltages come from pressing the buttons, so every
+    // machine can be solved.
+    lines((0..size).map(|_| {
+        let lights = rng.range(3..=10);
+        let buttons = (0..rng.range(lights - 2..=lights + 3))
+            .map(|_| {
+                let mut wired = (0..lights as usize).collect_vec();
+                rng.shuffle(&mut wired);
+                wired.truncate(rng.range(1..=lights) as usize);
+                wired.sort_unstable();
+                wired
+            })
+            .collect_vec();
+
+        let mut target = vec!['.'; lights as usize];
+        let mut joltage = vec![0; lights as usize];
+        for button in &buttons {
+            let toggled = rng.chance(50);
+            let presses = rng.range(0..=30);
+            for &light in button {
+                if toggled {
+                    target[light] = if target[light] == '#' { '.' } else { '#' };
+                }
+                joltage[light] += presses;
+            }
+        }
+        format!(
+            "[{}] {} {{{}}}",
+            target.into_iter().collect::<String>(),
+            buttons
+                .iter()
+                .map(|button| format!("({})", button.iter().join(",")))
+                .join(" "),
+            joltage.iter().join(","),
+        )
+    }))
+}
+
+fn day11(rng: &mut Rng, size: usize) -> String {
+    // Layers of devices, each wired to between one and three devices in the
+    // next layer, so every path is as long as the layers are many. That
+    // bounds the path counts: at most 3^40 from `svr` and 3^19 from `you`.
+    const RESERVED: [&str; 5] = ["svr", "you", "fft", "dac", "out"];
+    let layers = size.isqrt().clamp(3, 40);
+    let per_layer = size.div_ceil(layers);
+
+    // Three letters like the real names, or more once those run short.
+    let letters = (3..).find(|&n| 26usize.pow(n) >= 2 * size).unwrap();
+    let mut names: FnvHashSet<String> = RESERVED.iter().map(|&name| name.into()).collect();
+    let mut name = || loop {
+        let name: String = (0..letters)
+            .map(|_| char::from(b'a' + rng.range(0..=25) as u8))
+            .collect();
+        if names.insert(name.clone()) {
+            return name;
+        }
+    };
+    let mut devices = vec![vec!["svr".to_string()]];
+    devices.extend((0..layers).map(|_| (0..per_layer).map(|_| name()).collect_vec()));
+    devices.push(vec!["out".to_string()]);
+
+    // One device per layer forms a path from `svr` to `out`, through `fft`
+    // and `dac` in either order.
+    let path = devices
+        .iter()
+        .map(|layer| rng.below(layer.len()))
+        .collect_vec();
+    let mut first = 1 + rng.below(layers / 2);
+    let mut second = 1 + layers / 2 + rng.below(layers - layers / 2);
+    if rng.chance(50) {
+        (first, second) = (second, first);
+    }
+    devices[first][path[first]] = "fft".into();
+    devices[second][path[second]] = "dac".into();
+    let you = layers - rng.below(layers.min(20));
+    devices[you].push("you".into());
+
+    let mut wiring = devices
+        .iter()
+        .tuple_windows()
+        .zip(path.iter().tuple_windows())
+        .flat_map(|((layer, next), (&on_path, &next_on_path))| {
+            layer
+                .iter()
+                .enumerate()
+                .map(|(i, device)| {
+                    let mut outputs = (0..next.len()).collect_vec();
+                    rng.shuffle(&mut outputs);
+                    outputs.truncate(rng.range(1..=3) as usize);
+                    if i == on_path && !outputs.contains(&next_on_path) {
+                        outputs[0] = next_on_path;
+                    }
+                    format!(
+                        "{device}: {}",
+                        outputs.into_iter().map(|j| &next[j]).join(" ")
+                    )
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+    rng.shuffle(&mut wiring);
+    lines(wiring)
+}
+
+fn day12(rng: &mut Rng, size: usize) -> String {
+    const SHAPES: usize = 6;
+    let shapes = (0..SHAPES)
+        .map(|_| {
+            let mut tiles = (0..9).collect_vec();
+            rng.shuffle(&mut tiles);
+            tiles.truncate(rng.range(5..=8) as usize);
+            tiles
+        })
+        .collect_vec();
+
+    // As in the real inputs, every region either has room for each present
+    // in its own 3x3 square or has fewer tiles than the presents need.
+    let mut regions = String::new();
+    for _ in 0..size {
+        let (w, h) = (rng.range(35..=50), rng.range(35..=50));
+        let mut counts = [0u64; SHAPES];
+        if rng.chance(50) {
+            for _ in 0..rng.range(0..=(w / 3) * (h / 3)) {
+                counts[rng.below(SHAPES)] += 1;
+            }
+        } else {
+            let mut tiles = 0;
+            while tiles <= w * h {
+                let shape = rng.below(SHAPES);
+                counts[shape] += 1;
+                tiles += shapes[shape].len() as u64;
+            }
+        }
+        writeln!(regions, "{w}x{h}: {}", counts.iter().join(" ")).unwrap();
+    }
+
+    let shapes = shapes.iter().enumerate().map(|(i, tiles)| {
+        let rows = (0..3).map(|y| {
+            (0..3)
+                .map(|x| {
+                    if tiles.contains(&(y * 3 + x)) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        });
+        format!("{i}:\n{}", lines(rows))
+    });
+    format!("{}\n{regions}", shapes.format("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SOLUTIONS;
+
+    #[test]
+    fn test_every_day_has_a_generator() {
+        let days = GENERATORS
+            .iter()
+            .map(|generator| generator.day)
+            .collect_vec();
+        let solved = SOLUTIONS
+            .iter()
+            .map(|solution| solution.day())
+            .collect_vec();
+        assert_eq!(days, solved);
+        assert_eq!(generate(26, 0, 10), Err(UnknownDay(26)));
+    }
+
+    #[test]
+    fn test_max_size() {
+        for generator in GENERATORS {
+            assert!(
+                generator.max_size >= generator.default_size,
+                "day {}",
+                generator.day
+            );
+        }
+    }
+
+    #[test]
+    fn test_deterministic() {
+        for generator in GENERATORS {
+            let day = generator.day;
+            assert_eq!(generate(day, 7, 20), generate(day, 7, 20));
+            assert_ne!(generate(day, 7, 20), generate(day, 8, 20), "day {day}");
+        }
+    }
+
+    #[test]
+    fn test_inputs_are_valid() {
+        for solution in SOLUTIONS {
+            let examples = crate::examples::for_day(solution.day()).unwrap();
+            for (seed, size) in [(0, 1), (1, 4), (2, 30)] {
+                let input = generate(solution.day(), seed, size).unwrap();
+                for &part in solution.parts() {
+                    // A day just added has stubs for both its parts and its
+                    // generator until it has example answers.
+                    if examples.iter().all(|e| e.expected(part).is_none()) {
+                        continue;
+                    }
+                    if let Some(Err(e)) = solution.solve(part, input.as_bytes()) {
+                        panic!("seed {seed}, size {size}: {e}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(1);
+        assert!((0..1000).all(|_| (3..=5).contains(&rng.range(3..=5))));
+        assert_eq!(rng.range(9..=9), 9);
+        rng.range(0..=u64::MAX);
+    }
+}
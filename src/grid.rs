@@ -0,0 +1,253 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use bstr::ByteSlice;
+
+use crate::ParseError;
+
+/// `(x, y)` with `x` counting columns and `y` counting rows from the top left.
+pub type Pos = (usize, usize);
+
+/// Up, left, right, down.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The 4 orthogonal and 4 diagonal directions in reading order.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses one cell per byte and one row per line, mapping each byte with
+    /// `cell`. Bytes it rejects and ragged rows are reported against `input`.
+    pub fn parse(
+        day: u8,
+        input: &[u8],
+        expected: &'static str,
+        mut cell: impl FnMut(u8) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut lines = input.lines().peekable();
+        let width = lines.peek().map_or(0, |row| row.len());
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for row in lines {
+            if row.len() != width {
+                return Err(ParseError::new(
+                    day,
+                    input,
+                    row,
+                    format!("a row of width {width}"),
+                ));
+            }
+            for (x, &c) in row.iter().enumerate() {
+                let value =
+                    cell(c).ok_or_else(|| ParseError::new(day, input, &row[x..x + 1], expected))?;
+                cells.push(value);
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// `pos` moved by `(dx, dy)`, if that is still inside the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// The orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_4
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that lie inside the
+    /// grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_8
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// Every position in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// # Panics
+    ///
+    /// If `y` is not below [`Grid::height`].
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[T]> {
+        // `chunks_exact` rejects 0, and a grid without columns has no cells.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// # Panics
+    ///
+    /// If `x` is not below [`Grid::width`].
+    pub fn column(&self, x: usize) -> impl ExactSizeIterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// The first position holding `value` in reading order.
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        let i = self.cells.iter().position(|cell| cell == value)?;
+        Some((i % self.width, i / self.width))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of bounds"))
+    }
+}
+
+impl<T: Copy + Into<char>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for &cell in row {
+                write!(f, "{}", cell.into())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The byte of `input` that the cell at `pos` of [`Grid::parse`]'s result was
+/// read from, for pointing a [`ParseError`] at it.
+pub fn source_cell(input: &[u8], (x, y): Pos) -> &[u8] {
+    let row = input.lines().nth(y).unwrap_or_default();
+    &row[x.min(row.len())..(x + 1).min(row.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] = b"#.S\n...\n.##\n";
+
+    fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(0, input, "`.`, `#` or `S`", |c| {
+            matches!(c, b'.' | b'#' | b'S').then_some(c)
+        })
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = parse(EXAMPLE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get((2, 0)), Some(&b'S'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.find(&b'S'), Some((2, 0)));
+        assert_eq!(grid.find(&b'x'), None);
+        assert_eq!(grid.to_string().as_bytes(), EXAMPLE);
+
+        let err = parse(b"#.S\n..\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = parse(b"#.S\n.x.\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(source_cell(b"#.S\n.x.\n", (1, 1)), b"x");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = parse(EXAMPLE).unwrap();
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), [
+            (1, 0),
+            (0, 1)
+        ]);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 2)).collect::<Vec<_>>(), [
+            (1, 1),
+            (2, 1),
+            (1, 2)
+        ]);
+    }
+
+    #[test]
+    fn test_views() {
+        let mut grid = parse(EXAMPLE).unwrap();
+        grid[(1, 1)] = b'#';
+        assert_eq!(grid.row(1), b".#.");
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b".##");
+        assert_eq!(grid.rows().len(), 3);
+        assert_eq!(grid.positions().nth(4), Some((1, 1)));
+
+        let empty = parse(b"").unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.rows().count(), 0);
+    }
+}
@@ -1,10 +1,21 @@
-#![feature(int_roundings, concat_bytes)]
+#![feature(int_roundings)]
+
+use std::{
+    borrow::Cow,
+    io,
+    path::{Path, PathBuf},
+};
 
 use mimalloc_rust::GlobalMiMalloc;
 
 #[global_allocator]
 static GLOBAL_MIMALLOC: GlobalMiMalloc = GlobalMiMalloc;
 
+pub mod answers;
+pub mod batch;
+pub mod bench;
+pub mod cache;
+pub mod client;
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -17,3 +28,110 @@ pub mod day09;
 pub mod day10;
 pub mod day11;
 pub mod day12;
+pub mod error;
+pub mod examples;
+pub mod r#gen;
+pub mod grid;
+pub mod parse;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod stream;
+pub mod threads;
+#[cfg(target_os = "linux")]
+pub mod watch;
+
+pub use error::ParseError;
+pub use solution::{Answer, Part, Solution, UnknownDay};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+];
+
+pub fn solution(day: u8) -> Result<&'static dyn Solution, UnknownDay> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .find(|solution| solution.day() == day)
+        .ok_or(UnknownDay(day))
+}
+
+/// Location of the puzzle input for `day` inside the `inputs/` directory.
+pub fn input_path(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("inputs/day{day:02}.txt"))
+}
+
+/// Puzzle input for `day`: the embedded copy with the `embedded-inputs`
+/// feature, otherwise read from [`input_path`] at runtime.
+pub fn load_input(day: u8) -> io::Result<Cow<'static, [u8]>> {
+    #[cfg(feature = "embedded-inputs")]
+    {
+        let embedded = match day {
+            1 => Some(day01::INPUT),
+            2 => Some(day02::INPUT),
+            3 => Some(day03::INPUT),
+            4 => Some(day04::INPUT),
+            5 => Some(day05::INPUT),
+            6 => Some(day06::INPUT),
+            7 => Some(day07::INPUT),
+            8 => Some(day08::INPUT),
+            9 => Some(day09::INPUT),
+            10 => Some(day10::INPUT),
+            11 => Some(day11::INPUT),
+            12 => Some(day12::INPUT),
+            _ => None,
+        };
+        if let Some(input) = embedded {
+            return Ok(Cow::Borrowed(input));
+        }
+    }
+
+    std::fs::read(input_path(day)).map(Cow::Owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_sorted_by_day() {
+        let days: Vec<u8> = SOLUTIONS.iter().map(|solution| solution.day()).collect();
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "{days:?}");
+    }
+
+    #[test]
+    fn test_undeclared_parts_are_unavailable() {
+        for solution in SOLUTIONS {
+            for &part in Part::BOTH {
+                if !solution.parts().contains(&part) {
+                    assert_eq!(solution.solve(part, b""), None);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        let err = solution(26).err().unwrap().to_string();
+        // Days added later go on the end.
+        assert!(
+            err.starts_with(
+                "Day 26 is not implemented; available days: 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12"
+            ),
+            "{err}"
+        );
+    }
+}
@@ -1,27 +1,991 @@
-use clap::Parser;
+#[cfg(target_os = "linux")]
+use std::collections::BTreeMap;
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{BufRead, BufReader, Read, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+#[cfg(target_os = "linux")]
+use aoc_2025::watch;
+use aoc_2025::{
+    Part, SOLUTIONS,
+    answers::{self, Answers, Status},
+    batch,
+    bench::{self, Baseline, Change},
+    cache::Cache,
+    client::{self, Client, Fetched, Submission, Submissions},
+    examples::{self, Example},
+    r#gen, repl,
+    report::{self, Format},
+    runner::{self, DayRun, PartRun},
+    scaffold,
+    threads::{self, Threads},
+};
+use clap::{CommandFactory, Parser, error::ErrorKind};
 
 #[derive(clap::Parser)]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
-    day: u8,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(required_unless_present = "list")]
+    day: Option<u8>,
+    /// Read the puzzle input from PATH instead of `inputs/dayNN.txt` (`-`
+    /// for stdin)
+    #[arg(long, value_name = "PATH|-")]
+    input: Option<PathBuf>,
+    /// Run on the day's examples in `examples/` instead, checking their
+    /// answers
+    #[arg(long, conflicts_with = "input")]
+    example: bool,
+    /// Keep running the day whenever its input, or with `--example` its
+    /// examples, change
+    #[cfg(target_os = "linux")]
+    #[arg(long, conflicts_with = "stream")]
+    watch: bool,
+    /// Solve the input as it is read, in bounded memory, printing only the
+    /// answers (days 1, 3, 5 and 10)
+    #[arg(long, conflicts_with_all = ["example", "format"])]
+    stream: bool,
+    /// List the implemented days and exit
+    #[arg(long)]
+    list: bool,
+    /// Output format for answers and timings
+    #[arg(long, value_enum, global = true, default_value_t)]
+    format: Format,
+    /// Run the solutions on a pool of N threads [default: one per CPU]
+    #[arg(long, value_name = "N", global = true)]
+    threads: Option<NonZeroUsize>,
+    /// Run everything in order on the main thread
+    #[arg(long, global = true, conflicts_with = "threads")]
+    sequential: bool,
+    /// Solve every part again instead of taking its answer from the cache
+    /// in `~/.cache/aoc-2025/`, and leave the cache as it is
+    #[arg(long, global = true)]
+    no_cache: bool,
+}
+
+impl Args {
+    /// The first argument given that only applies without a subcommand.
+    fn day_arg(&self) -> Option<&'static str> {
+        let mut given = vec![
+            ("[DAY]", self.day.is_some()),
+            ("--input", self.input.is_some()),
+            ("--example", self.example),
+            ("--stream", self.stream),
+            ("--list", self.list),
+        ];
+        #[cfg(target_os = "linux")]
+        given.push(("--watch", self.watch));
+        given
+            .into_iter()
+            .find_map(|(arg, given)| given.then_some(arg))
+    }
+
+    fn threads(&self) -> Threads {
+        match self.threads {
+            _ if self.sequential => Threads::Sequential,
+            Some(n) => Threads::Pool(n.get()),
+            None => Threads::Global,
+        }
+    }
+}
+
+#[derive(clap::Args)]
+struct ClientArgs {
+    /// Base URL of the puzzle site
+    #[arg(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Read the session token from PATH [default: $AOC_SESSION, then
+    /// `.session` in the crate root]
+    #[arg(long, value_name = "PATH")]
+    session_file: Option<PathBuf>,
+}
+
+impl ClientArgs {
+    fn client(&self) -> Client {
+        let session = client::read_session(self.session_file.as_deref()).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        Client::new(&self.base_url, session)
+    }
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Run every implemented day and print a timing table, failing if any
+    /// part does
+    All {
+        /// Run the days concurrently
+        #[arg(long)]
+        parallel: bool,
+    },
+    /// Check every day's answers against the expected answers file, solving
+    /// every part again rather than taking answers from the cache
+    Verify {
+        /// Expected answers in TOML [default: answers.toml in the crate root]
+        #[arg(long, value_name = "PATH")]
+        answers: Option<PathBuf>,
+        /// Run the days concurrently
+        #[arg(long)]
+        parallel: bool,
+    },
+    /// Download a day's puzzle input into `inputs/` unless it already exists
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[command(flatten)]
+        client: ClientArgs,
+    },
+    /// Solve one part and submit the answer, unless the submissions log
+    /// already knows how it will be judged
+    Submit {
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Submissions log [default: submissions.jsonl in the crate root]
+        #[arg(long, value_name = "PATH")]
+        log: Option<PathBuf>,
+        #[command(flatten)]
+        client: ClientArgs,
+    },
+    /// Run a day against every file in a directory, reporting panics
+    /// instead of stopping at them
+    Batch { day: u8, dir: PathBuf },
+    /// Time every part on its input, saving the timings as a baseline in
+    /// `benches/baselines/` or comparing them with one
+    Bench {
+        /// Save the timings as baseline NAME
+        #[arg(long, value_name = "NAME")]
+        save: Option<String>,
+        /// Compare the timings with baseline NAME, failing if any part
+        /// regressed
+        #[arg(long, value_name = "NAME")]
+        compare: Option<String>,
+        /// Runs per part, of which the median counts
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        iterations: u32,
+        /// How many percent slower than the baseline counts as a regression
+        #[arg(long, value_name = "PERCENT", default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Explore a day's parsed input interactively
+    Repl {
+        day: u8,
+        /// Read the puzzle input from PATH instead of `inputs/dayNN.txt`
+        #[arg(long, value_name = "PATH")]
+        input: Option<PathBuf>,
+    },
+    /// Add a day: its module, an empty example, a generator stub, an answer
+    /// test, a fuzz target and their registration
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The puzzle's title [default: "Day NN"]
+        #[arg(long)]
+        title: Option<String>,
+        /// Root of the crate to add the day to [default: this crate]
+        #[arg(long, value_name = "PATH")]
+        root: Option<PathBuf>,
+    },
+    /// Print a random input for a day, the same one for the same seed and
+    /// size
+    Gen {
+        day: u8,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Number of lines, ranges, machines or devices, or the grid's side
+        /// length [default: about as many as the real input]
+        #[arg(long)]
+        size: Option<usize>,
+    },
+}
+
+fn read_input(path: &Path) -> std::io::Result<Vec<u8>> {
+    if path == Path::new("-") {
+        let mut buf = Vec::new();
+        std::io::stdin().lock().read_to_end(&mut buf)?;
+        Ok(buf)
+    } else {
+        std::fs::read(path)
+    }
+}
+
+fn print_errors(runs: &[DayRun]) {
+    for run in runs {
+        for part in run.parts.iter().flatten() {
+            if let Err(e) = &part.answer {
+                eprintln!("error: {e}");
+            }
+        }
+    }
+}
+
+fn print_runs(format: Format, runs: &[DayRun]) {
+    let stdout = std::io::stdout().lock();
+    match format {
+        Format::Text => report::write_table(stdout, runs),
+        Format::Json => report::write_json(stdout, runs),
+        Format::Tsv => report::write_tsv(stdout, runs),
+    }
+    .expect("failed to write to stdout");
+}
+
+/// The answer cache, unless `--no-cache` is given or this build cannot use
+/// one.
+fn open_cache(no_cache: bool) -> Option<Cache> {
+    if no_cache {
+        return None;
+    }
+    match Cache::open()? {
+        Ok(cache) => Some(cache),
+        Err(e) => {
+            eprintln!("warning: not using the answer cache: {e}");
+            None
+        }
+    }
+}
+
+/// Warns about inputs identical to another day's, which usually means one
+/// was pasted into the wrong file.
+fn warn_duplicate_inputs(runs: &[DayRun], cache: Option<&Cache>) {
+    for (i, run) in runs.iter().enumerate() {
+        let Some(input_hash) = run.input_hash else {
+            continue;
+        };
+        let day = run.solution.day();
+        let mut days = cache.map_or_else(Vec::new, |cache| cache.other_days(day, input_hash));
+        days.extend(
+            runs[..i]
+                .iter()
+                .filter(|other| other.input_hash == Some(input_hash))
+                .map(|other| other.solution.day()),
+        );
+        days.sort_unstable();
+        days.dedup();
+        for other in days {
+            eprintln!("warning: the day {day:02} input is identical to the day {other:02} input");
+        }
+    }
+}
+
+/// Warns about duplicate inputs, then adds the new answers in `runs` to
+/// `cache` and saves it.
+fn update_cache(mut cache: Option<Cache>, runs: &[DayRun]) {
+    warn_duplicate_inputs(runs, cache.as_ref());
+    let Some(cache) = &mut cache else {
+        return;
+    };
+    for run in runs {
+        cache.record(run);
+    }
+    if let Err(e) = cache.save() {
+        eprintln!("warning: failed to save the answer cache: {e}");
+    }
+}
+
+fn verify(answers_path: Option<PathBuf>, parallel: bool, cache: Option<Cache>) {
+    // Only the default file may be missing: a path given explicitly that does
+    // not exist is a mistake, not a crate without answers yet.
+    let answers = match &answers_path {
+        Some(path) => Answers::read(path),
+        None => Answers::load(&answers::answers_path()),
+    };
+    let answers_path = answers_path.unwrap_or_else(answers::answers_path);
+    let answers = answers.unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to read answers from {}: {e}",
+            answers_path.display()
+        );
+        std::process::exit(1);
+    });
+
+    // Always solve again: a cached answer is what is being verified.
+    let runs = runner::run_all(parallel, None);
+    update_cache(cache, &runs);
+    let checks = answers::check(&runs, &answers);
+    for check in &checks {
+        let answer = check.answer.map(|a| a.to_string()).unwrap_or_default();
+        let status = match &check.status {
+            Status::Pass => "pass".to_string(),
+            Status::Fail { expected } => format!("FAIL (expected {expected})"),
+            Status::Missing => "missing (no expected answer)".to_string(),
+            Status::NoInput => "missing (no input)".to_string(),
+            Status::ParseError => "FAIL (parse error)".to_string(),
+        };
+        println!(
+            "Day {:02} part {}: {answer:>16}  {status}",
+            check.day, check.part
+        );
+    }
+    print_errors(&runs);
+
+    let count = |f: fn(&Status) -> bool| checks.iter().filter(|c| f(&c.status)).count();
+    let passed = count(|s| *s == Status::Pass);
+    let failed = checks.iter().filter(|check| check.is_failure()).count();
+    println!(
+        "{passed} passed, {failed} failed, {} missing",
+        count(|s| matches!(s, Status::Missing | Status::NoInput)),
+    );
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    if passed == 0 {
+        eprintln!(
+            "No answers were checked: {} has none for the days with an input",
+            answers_path.display()
+        );
+        std::process::exit(1);
+    }
+}
+
+fn fetch(day: u8, client: &ClientArgs) {
+    let path = aoc_2025::input_path(day);
+    if path.exists() {
+        println!(
+            "Input for day {day:02} already exists at {}",
+            path.display()
+        );
+        return;
+    }
+
+    match client::fetch_to(&client.client(), day, &path) {
+        Ok(Fetched::Downloaded) => {
+            println!("Downloaded input for day {day:02} to {}", path.display());
+        }
+        Ok(Fetched::Cached) => {
+            println!(
+                "Input for day {day:02} already exists at {}",
+                path.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to fetch input for day {day:02}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn submit(day: u8, part: Part, log: Option<PathBuf>, client: &ClientArgs) {
+    let solution = aoc_2025::solution(day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    let input = aoc_2025::load_input(day).unwrap_or_else(|e| {
+        let path = aoc_2025::input_path(day);
+        eprintln!("Failed to read input from {}: {e}", path.display());
+        std::process::exit(1);
+    });
+    let answer = match solution.solve(part, &input) {
+        Some(Ok(answer)) => answer.to_string(),
+        Some(Err(e)) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("Day {day:02} has no part {part}");
+            std::process::exit(1);
+        }
+    };
+
+    let log_path = log.unwrap_or_else(client::submissions_path);
+    let mut log = Submissions::load(&log_path).unwrap_or_else(|e| {
+        eprintln!("Failed to read {}: {e}", log_path.display());
+        std::process::exit(1);
+    });
+    if let Some((verdict, previous)) = log.known(day, part, &answer) {
+        if previous.answer == answer {
+            println!("Day {day:02} part {part}: {answer} was already submitted: {verdict}");
+        } else {
+            println!(
+                "Day {day:02} part {part}: {answer} is {verdict}, judging by the earlier \
+                 submission {} ({})",
+                previous.answer, previous.verdict
+            );
+        }
+        if !verdict.is_done() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let verdict = client
+        .client()
+        .submit_answer(day, part, &answer)
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to submit day {day:02} part {part}: {e}");
+            std::process::exit(1);
+        });
+    println!("Day {day:02} part {part}: {answer} is {verdict}");
+
+    let submission = Submission {
+        day,
+        part: part.number(),
+        answer,
+        verdict: verdict.clone(),
+    };
+    if let Err(e) = log.record(submission) {
+        eprintln!("Failed to record submission in {}: {e}", log_path.display());
+    }
+    if !verdict.is_done() {
+        std::process::exit(1);
+    }
+}
+
+fn batch(day: u8, dir: &Path, format: Format) {
+    let solution = aoc_2025::solution(day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    let runs = batch::run_dir(solution, dir).unwrap_or_else(|e| {
+        eprintln!("Failed to read {}: {e}", dir.display());
+        std::process::exit(1);
+    });
+
+    let stdout = std::io::stdout().lock();
+    match format {
+        Format::Text => batch::write_table(stdout, &runs),
+        Format::Json => batch::write_json(stdout, &runs),
+        Format::Tsv => batch::write_tsv(stdout, &runs),
+    }
+    .expect("failed to write to stdout");
+
+    for run in &runs {
+        let path = run.path.display();
+        let parts = match &run.parts {
+            Ok(parts) => parts,
+            Err(e) => {
+                eprintln!("error: {path}: {e}");
+                continue;
+            }
+        };
+        for part in parts {
+            match part {
+                Ok(run) => {
+                    if let Err(e) = &run.answer {
+                        eprintln!("error: {path}: {e}");
+                    }
+                }
+                Err(panic) => {
+                    eprintln!("panic: {path}: part {}: {}", panic.part, panic.message);
+                }
+            }
+        }
+    }
+    let failed = runs.iter().filter(|run| run.is_failure()).count();
+    if failed > 0 {
+        eprintln!("{failed} of {} files failed", runs.len());
+        std::process::exit(1);
+    }
+}
+
+fn generate(day: u8, seed: u64, size: Option<usize>) {
+    let input = size
+        .map_or_else(|| r#gen::default_size(day), Ok)
+        .and_then(|size| r#gen::generate(day, seed, size))
+        .unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+    std::io::stdout()
+        .lock()
+        .write_all(input.as_bytes())
+        .expect("failed to write to stdout");
+}
+
+fn run_day(args: Args) {
+    let day = args
+        .day
+        .expect("clap requires a day unless --list is given");
+    let solution = aoc_2025::solution(day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+
+    let start = Instant::now();
+    let input = match &args.input {
+        Some(path) => read_input(path).map(Cow::Owned),
+        None => aoc_2025::load_input(day),
+    }
+    .unwrap_or_else(|e| {
+        let path = args.input.unwrap_or_else(|| aoc_2025::input_path(day));
+        eprintln!("Failed to read input from {}: {e}", path.display());
+        std::process::exit(1);
+    });
+    let cache = open_cache(args.no_cache);
+    let run = runner::run_input(solution, &input, start.elapsed(), cache.as_ref());
+    update_cache(cache, std::slice::from_ref(&run));
+
+    let parts = run.parts.as_deref().unwrap_or_default();
+    match args.format {
+        Format::Text => {
+            for part in parts {
+                if let Ok(answer) = &part.answer {
+                    let cached = if part.cached { " (cached)" } else { "" };
+                    println!("Part {}: {answer}{cached}", part.part);
+                }
+            }
+        }
+        format => print_runs(format, std::slice::from_ref(&run)),
+    }
+
+    print_errors(std::slice::from_ref(&run));
+    if parts.iter().any(|part| part.answer.is_err()) {
+        std::process::exit(1);
+    }
+}
+
+fn stream(args: &Args) {
+    let day = args
+        .day
+        .expect("clap requires a day unless --list is given");
+    let solution = aoc_2025::solution(day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+
+    let path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| aoc_2025::input_path(day));
+    let mut reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(std::io::stdin().lock())
+    } else {
+        match File::open(&path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("Failed to read input from {}: {e}", path.display());
+                std::process::exit(1);
+            }
+        }
+    };
+
+    match solution.solve_reader(&mut reader) {
+        Some(Ok(answers)) => {
+            let mut failed = false;
+            for (part, answer) in answers {
+                match answer {
+                    Ok(answer) => println!("Part {part}: {answer}"),
+                    Err(e) => {
+                        eprintln!("error: {}: {e}", path.display());
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Some(Err(e)) => {
+            eprintln!("error: {}: {e}", path.display());
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("Day {day} cannot stream its input");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// What is wrong with `part`'s answer to `example`, if anything.
+fn wrong_answer(example: &Example, part: &PartRun) -> Option<String> {
+    let answer = part.answer.as_ref().ok()?;
+    let expected = example.expected(part.part)?;
+    (answer.to_string() != expected).then(|| {
+        format!(
+            "{} part {}: expected {expected}, got {answer}",
+            example.file, part.part
+        )
+    })
+}
+
+fn run_examples(day: u8, format: Format) {
+    let solution = aoc_2025::solution(day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    let examples = examples::for_day(day).unwrap_or_else(|e| {
+        eprintln!("Failed to read examples/manifest.toml: {e}");
+        std::process::exit(1);
+    });
+    if examples.is_empty() {
+        eprintln!("Day {day} has no examples in examples/manifest.toml");
+        std::process::exit(1);
+    }
+
+    let mut runs = Vec::new();
+    let mut wrong = Vec::new();
+    for example in &examples {
+        let run = runner::run_example(solution, example).unwrap_or_else(|e| {
+            eprintln!("Failed to read {}: {e}", example.path().display());
+            std::process::exit(1);
+        });
+        let parts = run.parts.as_deref().unwrap_or_default();
+        wrong.extend(parts.iter().filter_map(|part| wrong_answer(example, part)));
+
+        if format == Format::Text {
+            println!("{}:", example.file);
+            for part in parts {
+                if let Ok(answer) = &part.answer {
+                    println!("  Part {}: {answer}", part.part);
+                }
+            }
+        }
+        runs.push(run);
+    }
+
+    if format != Format::Text {
+        print_runs(format, &runs);
+    }
+    print_errors(&runs);
+    for wrong in &wrong {
+        eprintln!("wrong: {wrong}");
+    }
+    let failed = runs
+        .iter()
+        .flat_map(|run| run.parts.iter().flatten())
+        .any(|part| part.answer.is_err());
+    if failed || !wrong.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+/// The runs for one pass of [`watch`], each with the example it solved.
+#[cfg(target_os = "linux")]
+fn watch_runs(
+    solution: &'static dyn aoc_2025::Solution,
+    args: &Args,
+) -> Vec<(Option<Example>, Result<DayRun, String>)> {
+    let day = solution.day();
+    if !args.example {
+        let path = args
+            .input
+            .clone()
+            .unwrap_or_else(|| aoc_2025::input_path(day));
+        let start = Instant::now();
+        let run = read_input(&path)
+            .map(|input| runner::run_input(solution, &input, start.elapsed(), None))
+            .map_err(|e| format!("Failed to read input from {}: {e}", path.display()));
+        return vec![(None, run)];
+    }
+
+    match examples::for_day(day) {
+        Ok(examples) => examples
+            .into_iter()
+            .map(|example| {
+                let run = runner::run_example(solution, &example)
+                    .map_err(|e| format!("Failed to read {}: {e}", example.path().display()));
+                (Some(example), run)
+            })
+            .collect(),
+        Err(e) => vec![(
+            None,
+            Err(format!("Failed to read examples/manifest.toml: {e}")),
+        )],
+    }
+}
+
+/// Runs the day, then again every time its input or examples change,
+/// comparing each part's time with the previous run.
+#[cfg(target_os = "linux")]
+fn watch(args: &Args) {
+    let day = args
+        .day
+        .expect("clap requires a day unless --list is given");
+    let solution = aoc_2025::solution(day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    let paths = if args.example {
+        let dir = examples::examples_dir();
+        vec![dir.join(format!("day{day:02}")), dir.join("manifest.toml")]
+    } else {
+        vec![
+            args.input
+                .clone()
+                .unwrap_or_else(|| aoc_2025::input_path(day)),
+        ]
+    };
+    let mut watcher = watch::Watcher::new(&paths).unwrap_or_else(|e| {
+        eprintln!("Failed to watch {}: {e}", paths[0].display());
+        std::process::exit(1);
+    });
+
+    let mut previous = BTreeMap::new();
+    loop {
+        for (example, run) in watch_runs(solution, args) {
+            let file = example.as_ref().map(|example| example.file.clone());
+            let indent = if let Some(file) = &file {
+                println!("{file}:");
+                "  "
+            } else {
+                ""
+            };
+            let parts = match &run {
+                Ok(run) => run.parts.as_deref().unwrap_or_default(),
+                Err(e) => {
+                    eprintln!("{e}");
+                    continue;
+                }
+            };
+
+            for part in parts {
+                let answer = match &part.answer {
+                    Ok(answer) => answer,
+                    Err(e) => {
+                        eprintln!("error: {e}");
+                        continue;
+                    }
+                };
+                let change = previous
+                    .insert((file.clone(), part.part), part.elapsed)
+                    .map(|before| format!(", {}", watch::change(before, part.elapsed)))
+                    .unwrap_or_default();
+                println!(
+                    "{indent}Part {}: {answer} ({:.2?}{change})",
+                    part.part, part.elapsed
+                );
+                if let Some(wrong) = example
+                    .as_ref()
+                    .and_then(|example| wrong_answer(example, part))
+                {
+                    eprintln!("wrong: {wrong}");
+                }
+            }
+        }
+
+        println!(
+            "Watching {} for changes",
+            paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        if let Err(e) = watcher.wait() {
+            eprintln!("Failed to watch for changes: {e}");
+            std::process::exit(1);
+        }
+        println!();
+    }
+}
+
+fn baseline_path(name: &str) -> PathBuf {
+    bench::baseline_path(name).unwrap_or_else(|| {
+        eprintln!("Invalid baseline name `{name}`: use letters, digits, `-`, `_` and `.`");
+        std::process::exit(1);
+    })
+}
+
+fn bench(save: Option<String>, compare: Option<String>, iterations: u32, threshold: f64) {
+    let save = save.as_deref().map(baseline_path);
+    let baseline = compare.as_deref().map(|name| {
+        let path = baseline_path(name);
+        Baseline::load(&path).unwrap_or_else(|e| {
+            eprintln!("Failed to read baseline from {}: {e}", path.display());
+            std::process::exit(1);
+        })
+    });
+
+    let mut failed = false;
+    let mut current = Baseline {
+        iterations,
+        timings: Vec::new(),
+    };
+    for &solution in SOLUTIONS {
+        let day = solution.day();
+        let input = match aoc_2025::load_input(day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!(
+                    "Skipping day {day:02}: {}: {e}",
+                    aoc_2025::input_path(day).display()
+                );
+                continue;
+            }
+        };
+        match bench::time_parts(solution, &input, iterations) {
+            Ok(timings) => current.timings.extend(timings),
+            Err(e) => {
+                eprintln!("error: {e}");
+                failed = true;
+            }
+        }
+    }
+
+    let mut regressed = 0;
+    if let Some(baseline) = &baseline {
+        println!(
+            "{:>3} {:>4} {:>10} {:>10} {:>8}",
+            "Day", "Part", "Median", "Baseline", "Change"
+        );
+        for comparison in bench::compare(baseline, &current, threshold) {
+            let (change, note) = match comparison.change {
+                Change::New => (String::new(), "new"),
+                Change::InputChanged => (String::new(), "input changed"),
+                Change::Within(percent) => (format!("{percent:+.1}%"), ""),
+                Change::Regressed(percent) => {
+                    regressed += 1;
+                    (format!("{percent:+.1}%"), "REGRESSED")
+                }
+            };
+            let before = comparison
+                .before
+                .map(|before| format!("{before:.2?}"))
+                .unwrap_or_default();
+            let row = format!(
+                "{:>3} {:>4} {:>10} {before:>10} {change:>8} {note}",
+                comparison.timing.day,
+                comparison.timing.part,
+                format!("{:.2?}", comparison.timing.median()),
+            );
+            println!("{}", row.trim_end());
+        }
+    } else {
+        println!("{:>3} {:>4} {:>10}", "Day", "Part", "Median");
+        for timing in &current.timings {
+            println!(
+                "{:>3} {:>4} {:>10}",
+                timing.day,
+                timing.part,
+                format!("{:.2?}", timing.median())
+            );
+        }
+    }
+
+    if let Some(path) = save {
+        if failed {
+            eprintln!("Not saving a baseline with failed parts");
+            std::process::exit(1);
+        }
+        if let Err(e) = current.save(&path) {
+            eprintln!("Failed to save baseline to {}: {e}", path.display());
+            std::process::exit(1);
+        }
+        println!("Saved baseline to {}", path.display());
+    }
+    if regressed > 0 {
+        println!("{regressed} parts regressed by more than {threshold}%");
+    }
+    if failed || regressed > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn explore(day: u8, input: Option<PathBuf>) {
+    let solution = aoc_2025::solution(day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    // Read the file every time, even with embedded inputs, so `reload`
+    // picks up edits.
+    let path = input.unwrap_or_else(|| aoc_2025::input_path(day));
+    repl::run(
+        solution,
+        || {
+            std::fs::read(&path)
+                .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {e}", path.display())))
+        },
+        std::io::stdin().lock(),
+        std::io::stdout().lock(),
+    )
+    .expect("failed to write to stdout");
+}
+
+fn new_day(day: u8, title: Option<String>, root: Option<PathBuf>) {
+    let root = root.unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+    let title = title.unwrap_or_else(|| format!("Day {day:02}"));
+    match scaffold::new_day(&root, day, &title) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to add day {day:02}: {e}");
+            std::process::exit(1);
+        }
+    }
 }
 
 fn main() {
     let args = Args::parse();
-    let day = args.day;
-
-    match day {
-        1 => aoc_2025::day01::run(),
-        2 => aoc_2025::day02::run(),
-        3 => aoc_2025::day03::run(),
-        4 => aoc_2025::day04::run(),
-        5 => aoc_2025::day05::run(),
-        6 => aoc_2025::day06::run(),
-        7 => aoc_2025::day07::run(),
-        8 => aoc_2025::day08::run(),
-        9 => aoc_2025::day09::run(),
-        10 => aoc_2025::day10::run(),
-        11 => aoc_2025::day11::run(),
-        12 => aoc_2025::day12::run(),
-        _ => unimplemented!("Day {day} not implemented yet"),
+    // Global flags may come before a subcommand, so clap cannot tell these
+    // apart from a run of a single day on its own.
+    if args.command.is_some()
+        && let Some(arg) = args.day_arg()
+    {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!("{arg} cannot be used with a subcommand"),
+            )
+            .exit();
+    }
+    threads::run(args.threads(), || dispatch(args));
+}
+
+fn dispatch(args: Args) {
+    match args.command {
+        Some(Command::All { parallel }) => {
+            let start = Instant::now();
+            let cache = open_cache(args.no_cache);
+            let runs = runner::run_all(parallel, cache.as_ref());
+            update_cache(cache, &runs);
+            print_runs(args.format, &runs);
+            print_errors(&runs);
+            if parallel && args.format == Format::Text {
+                println!("Wall time: {:.2?}", start.elapsed());
+            }
+            let failed = runs
+                .iter()
+                .flat_map(|run| run.parts.iter().flatten())
+                .any(|part| part.answer.is_err());
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Verify { answers, parallel }) => {
+            verify(answers, parallel, open_cache(args.no_cache))
+        }
+        Some(Command::Fetch { day, client }) => fetch(day, &client),
+        Some(Command::Submit {
+            day,
+            part,
+            log,
+            client,
+        }) => {
+            let part = Part::from_number(part).expect("clap restricts the part to 1 or 2");
+            submit(day, part, log, &client);
+        }
+        Some(Command::Batch { day, dir }) => batch(day, &dir, args.format),
+        Some(Command::Gen { day, seed, size }) => generate(day, seed, size),
+        Some(Command::New { day, title, root }) => new_day(day, title, root),
+        Some(Command::Repl { day, input }) => explore(day, input),
+        Some(Command::Bench {
+            save,
+            compare,
+            iterations,
+            threshold,
+        }) => bench(save, compare, iterations, threshold),
+        None if args.list => {
+            for solution in SOLUTIONS {
+                println!("Day {:02}: {}", solution.day(), solution.title());
+            }
+        }
+        #[cfg(target_os = "linux")]
+        None if args.watch => watch(&args),
+        None if args.stream => stream(&args),
+        None if args.example => run_examples(
+            args.day
+                .expect("clap requires a day unless --list is given"),
+            args.format,
+        ),
+        None => run_day(args),
     }
 }
@@ -0,0 +1,233 @@
+//! Checked integer parsing for puzzle inputs.
+//!
+//! Runs of 16 and 8 digits are converted a whole word at a time, so the long
+//! numbers in most inputs cost a handful of multiplications instead of one per
+//! digit.
+
+use crate::ParseError;
+
+/// Primitive integers the parsers can produce.
+pub trait Integer: Copy + PartialEq {
+    const ZERO: Self;
+    const SIGNED: bool;
+
+    fn from_u64(v: u64) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal: $($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const SIGNED: bool = $signed;
+
+            fn from_u64(v: u64) -> Option<Self> {
+                v.try_into().ok()
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_integer!(false: u8, u16, u32, u64, u128, usize);
+impl_integer!(true: i8, i16, i32, i64, i128, isize);
+
+const ONES: u64 = 0x0101_0101_0101_0101;
+
+/// Value of eight ASCII digits, or `None` if any byte is not a digit.
+pub fn parse_8_digits(chunk: [u8; 8]) -> Option<u32> {
+    let chunk = u64::from_le_bytes(chunk);
+    // A byte is a digit iff its high nibble is 3 and stays 3 after adding 6.
+    let high = 0xf0 * ONES;
+    if chunk & high != 0x30 * ONES || chunk.wrapping_add(0x06 * ONES) & high != 0x30 * ONES {
+        return None;
+    }
+
+    // The first digit sits in the lowest byte. Combine neighbouring digits
+    // into pairs, then pairs into a single value with two multiplications.
+    let digits = chunk - 0x30 * ONES;
+    let pairs = (digits * 10 + (digits >> 8)) & 0x00ff_00ff_00ff_00ff;
+    let mask = 0x0000_00ff_0000_00ff;
+    let value = (pairs & mask)
+        .wrapping_mul(100 + (1_000_000 << 32))
+        .wrapping_add(((pairs >> 16) & mask).wrapping_mul(1 + (10_000 << 32)))
+        >> 32;
+    Some(value as u32)
+}
+
+/// Value of sixteen ASCII digits, or `None` if any byte is not a digit.
+pub fn parse_16_digits(chunk: [u8; 16]) -> Option<u64> {
+    let (high, low) = chunk.split_at(8);
+    let high = parse_8_digits(high.try_into().unwrap())?;
+    let low = parse_8_digits(low.try_into().unwrap())?;
+    Some(u64::from(high) * 100_000_000 + u64::from(low))
+}
+
+/// Folds the digits of `s` into `T`, subtracting instead of adding for
+/// negative numbers so that `T::MIN` is reachable.
+fn accumulate<T: Integer>(mut s: &[u8], negative: bool) -> Option<T> {
+    if s.is_empty() {
+        return None;
+    }
+
+    let push = |acc: T, scale: u64, value: u64| {
+        // Leading zeros may come in chunks whose scale does not fit in `T`.
+        let acc = if acc == T::ZERO {
+            acc
+        } else {
+            acc.checked_mul(T::from_u64(scale)?)?
+        };
+        let value = T::from_u64(value)?;
+        if negative {
+            acc.checked_sub(value)
+        } else {
+            acc.checked_add(value)
+        }
+    };
+
+    let mut acc = T::ZERO;
+    while let Some((chunk, rest)) = s.split_first_chunk::<16>() {
+        acc = push(acc, 10_000_000_000_000_000, parse_16_digits(*chunk)?)?;
+        s = rest;
+    }
+    if let Some((chunk, rest)) = s.split_first_chunk::<8>() {
+        acc = push(acc, 100_000_000, parse_8_digits(*chunk)?.into())?;
+        s = rest;
+    }
+    for &c in s {
+        if !c.is_ascii_digit() {
+            return None;
+        }
+        acc = push(acc, 10, (c - b'0').into())?;
+    }
+    Some(acc)
+}
+
+/// Parses a non-empty run of ASCII digits, or `None` if `s` contains anything
+/// else or the value does not fit in `T`.
+pub fn uint<T: Integer>(s: &[u8]) -> Option<T> {
+    accumulate(s, false)
+}
+
+/// Like [`uint`], but also accepts a leading `-` for signed `T`.
+pub fn int<T: Integer>(s: &[u8]) -> Option<T> {
+    match s {
+        [b'-', digits @ ..] if T::SIGNED => accumulate(digits, true),
+        _ => accumulate(s, false),
+    }
+}
+
+/// [`uint`] with a [`ParseError`] pointing at `s` on failure.
+pub fn expect_uint<T: Integer>(
+    day: u8,
+    input: &[u8],
+    s: &[u8],
+    expected: &'static str,
+) -> Result<T, ParseError> {
+    uint(s).ok_or_else(|| ParseError::new(day, input, s, expected))
+}
+
+/// Every integer in `s`, skipping whatever separates them. A `-` directly in
+/// front of the digits is a sign when `T` is signed.
+///
+/// Yields `Err` with the offending digits when a number does not fit in `T`.
+pub fn integers<T: Integer>(s: &[u8]) -> Integers<'_, T> {
+    Integers {
+        rest: s,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+pub struct Integers<'a, T> {
+    rest: &'a [u8],
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<'a, T: Integer> Iterator for Integers<'a, T> {
+    type Item = Result<T, &'a [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.rest.iter().position(u8::is_ascii_digit)?;
+        let len = self.rest[start..]
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len() - start);
+        let negative = T::SIGNED && start > 0 && self.rest[start - 1] == b'-';
+        let sign = start - negative as usize;
+
+        let token = &self.rest[sign..start + len];
+        self.rest = &self.rest[start + len..];
+        Some(int(token).ok_or(token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_chunks() {
+        assert_eq!(parse_8_digits(*b"12345678"), Some(12_345_678));
+        assert_eq!(parse_8_digits(*b"00000000"), Some(0));
+        assert_eq!(parse_8_digits(*b"99999999"), Some(99_999_999));
+        for bad in [b"1234567/", b"1234567:", b"1234 678", b"\xff2345678"] {
+            assert_eq!(parse_8_digits(*bad), None);
+        }
+        assert_eq!(
+            parse_16_digits(*b"1234567890123456"),
+            Some(1_234_567_890_123_456)
+        );
+        assert_eq!(parse_16_digits(*b"12345678901234a6"), None);
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(uint::<u64>(b"18446744073709551615"), Some(u64::MAX));
+        assert_eq!(uint::<u64>(b"18446744073709551616"), None);
+        assert_eq!(uint::<u16>(b"65535"), Some(u16::MAX));
+        assert_eq!(uint::<u16>(b"65536"), None);
+        assert_eq!(uint::<u8>(b"00000000000255"), Some(255));
+        assert_eq!(uint::<u32>(b""), None);
+        assert_eq!(uint::<u32>(b"12a"), None);
+        assert_eq!(uint::<i32>(b"-1"), None);
+
+        assert_eq!(int::<i64>(b"-9223372036854775808"), Some(i64::MIN));
+        assert_eq!(int::<i64>(b"9223372036854775808"), None);
+        assert_eq!(int::<i8>(b"-128"), Some(-128));
+        assert_eq!(int::<i8>(b"-"), None);
+        assert_eq!(int::<u8>(b"-1"), None);
+    }
+
+    #[test]
+    fn test_integers() {
+        let all = |s| integers::<u64>(s).collect::<Result<Vec<_>, _>>();
+        assert_eq!(all(b"162,817,812"), Ok(vec![162, 817, 812]));
+        assert_eq!(
+            all(b"[.##.] (3) (1,3) {3,5,4,7}"),
+            Ok(vec![3, 1, 3, 3, 5, 4, 7])
+        );
+        assert_eq!(all(b"12x2: 4 0 1"), Ok(vec![12, 2, 4, 0, 1]));
+        assert_eq!(all(b"3-5"), Ok(vec![3, 5]));
+        assert_eq!(all(b"no numbers"), Ok(vec![]));
+        assert_eq!(
+            all(b"1,99999999999999999999"),
+            Err(&b"99999999999999999999"[..])
+        );
+
+        let signed = integers::<i32>(b"x=-3..5, L-12").collect::<Result<Vec<_>, _>>();
+        assert_eq!(signed, Ok(vec![-3, 5, -12]));
+    }
+}
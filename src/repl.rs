@@ -0,0 +1,248 @@
+//! An interactive session on one day's input, for looking at what the
+//! solution sees without editing tests to print it.
+//!
+//! The input is read and parsed once, then kept until `reload`. Days add
+//! their own commands through [`Solution::explore`].
+
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+    panic::{self, AssertUnwindSafe},
+    str::FromStr,
+    time::Instant,
+};
+
+use crate::{Part, Solution, batch::panic_message, solution::Params};
+
+/// A day's parsed input and the commands to query it.
+pub trait Explore {
+    /// Usage and description of each command.
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Runs `command` on `args`, or returns `None` if it is not one of
+    /// [`commands`](Self::commands).
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>>;
+}
+
+/// Parses the `i`th argument, called `name` in the error.
+pub fn arg<T: FromStr>(args: &[&str], i: usize, name: &str) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    let arg = args.get(i).ok_or_else(|| format!("missing {name}"))?;
+    arg.parse()
+        .map_err(|e| format!("invalid {name} `{arg}`: {e}"))
+}
+
+const COMMANDS: &[(&str, &str)] = &[
+    ("part N", "solve part N with the current parameters"),
+    ("param [NAME[=VALUE]]", "list, set or clear a parameter"),
+    ("reload", "read and parse the input again"),
+    ("help", "list the commands"),
+    ("quit", "leave"),
+];
+
+fn write_help(out: &mut impl Write, explorer: Option<&dyn Explore>) -> io::Result<()> {
+    let commands = COMMANDS
+        .iter()
+        .chain(explorer.map_or(&[][..], |explorer| explorer.commands()));
+    let width = commands
+        .clone()
+        .map(|(usage, _)| usage.len())
+        .max()
+        .unwrap();
+    for (usage, description) in commands {
+        writeln!(out, "  {usage:<width$}  {description}")?;
+    }
+    Ok(())
+}
+
+fn solve(
+    out: &mut impl Write,
+    solution: &dyn Solution,
+    input: &[u8],
+    params: &Params,
+    args: &[&str],
+) -> io::Result<()> {
+    let part = match arg(args, 0, "part") {
+        Ok(n) => Part::from_number(n),
+        Err(e) => return writeln!(out, "error: {e}"),
+    };
+    let Some(part) = part.filter(|part| solution.parts().contains(part)) else {
+        return writeln!(out, "error: day {} has no part {}", solution.day(), args[0]);
+    };
+
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        solution.solve_with(part, input, params)
+    }));
+    let elapsed = start.elapsed();
+    match result {
+        Ok(Some(Ok(answer))) => writeln!(out, "Part {part}: {answer} ({elapsed:.2?})"),
+        Ok(Some(Err(e))) => writeln!(out, "error: {e}"),
+        Ok(None) => unreachable!("the solution declares this part"),
+        Err(payload) => writeln!(out, "panic: {}", panic_message(&*payload)),
+    }
+}
+
+fn set_param(out: &mut impl Write, params: &mut Params, args: &[&str]) -> io::Result<()> {
+    match args {
+        [] => {
+            for (name, value) in params.iter() {
+                writeln!(out, "{name}={value}")?;
+            }
+            Ok(())
+        }
+        [param] => match param.split_once('=') {
+            Some((name, value)) => match value.parse() {
+                Ok(value) => {
+                    params.insert(name.to_string(), value);
+                    Ok(())
+                }
+                Err(e) => writeln!(out, "error: invalid value `{value}`: {e}"),
+            },
+            None => {
+                params.remove(*param);
+                Ok(())
+            }
+        },
+        _ => writeln!(out, "error: expected `param NAME=VALUE`"),
+    }
+}
+
+/// Runs the session for `solution`, reading commands from `commands` until
+/// they end or say `quit`. `load` reads the input, at the start and on
+/// every `reload`.
+pub fn run(
+    solution: &dyn Solution,
+    mut load: impl FnMut() -> io::Result<Vec<u8>>,
+    commands: impl BufRead,
+    mut out: impl Write,
+) -> io::Result<()> {
+    let mut params = Params::new();
+    let mut lines = commands.lines();
+
+    'load: loop {
+        let input = match load() {
+            Ok(input) => input,
+            Err(e) => return writeln!(out, "error: failed to read the input: {e}"),
+        };
+        let start = Instant::now();
+        let explorer = match solution.explore(&input) {
+            Some(Ok(explorer)) => Some(explorer),
+            Some(Err(e)) => {
+                writeln!(out, "error: {e}")?;
+                None
+            }
+            None => None,
+        };
+        writeln!(
+            out,
+            "Day {:02}: {} bytes, parsed in {:.2?}",
+            solution.day(),
+            input.len(),
+            start.elapsed()
+        )?;
+
+        loop {
+            write!(out, "day{:02}> ", solution.day())?;
+            out.flush()?;
+            let Some(line) = lines.next() else {
+                return writeln!(out);
+            };
+            let line = line?;
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            let Some((&command, args)) = words.split_first() else {
+                continue;
+            };
+
+            match command {
+                "quit" | "exit" => return Ok(()),
+                "reload" => continue 'load,
+                "help" => write_help(&mut out, explorer.as_deref())?,
+                "part" => solve(&mut out, solution, &input, &params, args)?,
+                "param" => set_param(&mut out, &mut params, args)?,
+                _ => match explorer
+                    .as_ref()
+                    .and_then(|explorer| explorer.run(command, args))
+                {
+                    Some(Ok(output)) => writeln!(out, "{output}")?,
+                    Some(Err(e)) => writeln!(out, "error: {e}")?,
+                    None => writeln!(out, "error: unknown command `{command}`; try `help`")?,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day05::Day05, day08::Day08, examples::examples_dir};
+
+    fn session(solution: &dyn Solution, input: &str, commands: &str) -> Vec<String> {
+        let mut out = Vec::new();
+        run(
+            solution,
+            || Ok(input.as_bytes().to_vec()),
+            commands.as_bytes(),
+            &mut out,
+        )
+        .unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .split(&format!("day{:02}> ", solution.day()))
+            .skip(1)
+            .map(|output| output.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_params() {
+        let input = std::fs::read_to_string(examples_dir().join("day08/example.txt")).unwrap();
+        let outputs = session(
+            &Day08,
+            &input,
+            "param steps=10\nparam\npart 1\nparam steps\npart 1\nparam steps=x\n",
+        );
+        assert_eq!(outputs[1], "steps=10");
+        assert!(outputs[2].starts_with("Part 1: 40 ("), "{}", outputs[2]);
+        assert!(outputs[4].starts_with("Part 1: "), "{}", outputs[4]);
+        assert!(outputs[5].starts_with("error: invalid value `x`"));
+    }
+
+    #[test]
+    fn test_commands() {
+        let outputs = session(
+            &Day05,
+            "3-5\n10-14\n12-18\n\n1\n",
+            "query 4\nquery 6\nquery\nranges\nfrob\npart 3\n\nquit\npart 1\n",
+        );
+        assert_eq!(outputs, [
+            "4 is fresh, in 3-5",
+            "6 is spoiled",
+            "error: missing ID",
+            "2 merged ranges, 12 IDs",
+            "error: unknown command `frob`; try `help`",
+            "error: day 5 has no part 3",
+            "",
+            "",
+        ]);
+    }
+
+    #[test]
+    fn test_reload() {
+        let mut inputs = ["3-5\n\n1", "1-2\n\n1"].into_iter();
+        let mut out = Vec::new();
+        run(
+            &Day05,
+            || Ok(inputs.next().unwrap().as_bytes().to_vec()),
+            "part 2\nreload\npart 2\n".as_bytes(),
+            &mut out,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Part 2: 3 ("), "{out}");
+        assert!(out.contains("Part 2: 2 ("), "{out}");
+    }
+}
@@ -0,0 +1,204 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::{Answer, runner::DayRun};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    /// Human-readable answers and timing table
+    #[default]
+    Text,
+    /// One JSON object per line for every day and part
+    Json,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+/// Flat, machine-readable result of one day and part.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    #[serde(rename = "type")]
+    pub answer_type: Option<&'static str>,
+    /// Part of `elapsed_ns` spent parsing the input.
+    pub parse_ns: Option<u128>,
+    pub elapsed_ns: Option<u128>,
+    /// Whether the answer came from the cache, without being timed.
+    pub cached: bool,
+    pub input_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+pub fn records(runs: &[DayRun]) -> Vec<Record> {
+    let mut records = Vec::new();
+    for run in runs {
+        let day = run.solution.day();
+        let input_hash = run.input_hash.map(|hash| format!("{hash:016x}"));
+
+        match &run.parts {
+            Ok(parts) => records.extend(parts.iter().map(|part| {
+                let answer = part.answer.as_ref().ok().copied();
+                Record {
+                    day,
+                    part: part.part.number(),
+                    answer,
+                    answer_type: answer.map(|answer| answer.type_name()),
+                    parse_ns: (!part.cached).then_some(part.parse.as_nanos()),
+                    elapsed_ns: (!part.cached).then_some(part.elapsed.as_nanos()),
+                    cached: part.cached,
+                    input_hash: input_hash.clone(),
+                    error: part.answer.as_ref().err().map(ToString::to_string),
+                }
+            })),
+            Err(e) => records.extend(run.solution.parts().iter().map(|part| Record {
+                day,
+                part: part.number(),
+                answer: None,
+                answer_type: None,
+                parse_ns: None,
+                elapsed_ns: None,
+                cached: false,
+                input_hash: None,
+                error: Some(format!("no input: {e}")),
+            })),
+        }
+    }
+    records
+}
+
+pub fn write_json(mut out: impl Write, runs: &[DayRun]) -> io::Result<()> {
+    for record in records(runs) {
+        serde_json::to_writer(&mut out, &record)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+pub fn write_tsv(mut out: impl Write, runs: &[DayRun]) -> io::Result<()> {
+    writeln!(
+        out,
+        "day\tpart\tanswer\ttype\tparse_ns\telapsed_ns\tcached\tinput_hash\terror"
+    )?;
+    for record in records(runs) {
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            record.day,
+            record.part,
+            record.answer.map(|a| a.to_string()).unwrap_or_default(),
+            record.answer_type.unwrap_or_default(),
+            record.parse_ns.map(|n| n.to_string()).unwrap_or_default(),
+            record.elapsed_ns.map(|n| n.to_string()).unwrap_or_default(),
+            record.cached,
+            record.input_hash.unwrap_or_default(),
+            // Only the summary line; the source excerpt spans several lines.
+            record
+                .error
+                .as_deref()
+                .and_then(|e| e.lines().next())
+                .unwrap_or_default(),
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_table(mut out: impl Write, runs: &[DayRun]) -> io::Result<()> {
+    writeln!(
+        out,
+        "{:<5} {:<22} {:>4} {:>16} {:>10} {:>10} {:>10}",
+        "Day", "Title", "Part", "Answer", "Read", "Parse", "Solve"
+    )?;
+
+    for run in runs {
+        let day = format!("{:02}", run.solution.day());
+        let title = run.solution.title();
+        let Ok(parts) = &run.parts else {
+            writeln!(
+                out,
+                "{day:<5} {title:<22} {:>4} {:>16} {:>10} {:>10} {:>10}",
+                "-", "no input", "-", "-", "-"
+            )?;
+            continue;
+        };
+
+        for (i, part) in parts.iter().enumerate() {
+            let answer = match &part.answer {
+                Ok(answer) => answer.to_string(),
+                Err(_) => "parse error".to_string(),
+            };
+            let (day, title, read) = if i == 0 {
+                (day.as_str(), title, format!("{:.2?}", run.load))
+            } else {
+                ("", "", String::new())
+            };
+            let (parse, solve) = if part.cached {
+                ("cached".to_string(), "cached".to_string())
+            } else {
+                (
+                    format!("{:.2?}", part.parse),
+                    format!("{:.2?}", part.solve()),
+                )
+            };
+            writeln!(
+                out,
+                "{day:<5} {title:<22} {:>4} {answer:>16} {read:>10} {parse:>10} {solve:>10}",
+                part.part
+            )?;
+        }
+    }
+
+    let total = runs.iter().map(DayRun::total).sum::<Duration>();
+    writeln!(out, "{:<77} {:>10}", "Total", format!("{total:.2?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day01::Day01, runner::run_input};
+
+    const EXAMPLE: &[u8] = b"L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    #[test]
+    fn test_json() {
+        let runs = [run_input(&Day01, EXAMPLE, Duration::ZERO, None)];
+        let mut out = Vec::new();
+        write_json(&mut out, &runs).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["day"], 1);
+        assert_eq!(lines[0]["part"], 1);
+        assert_eq!(lines[0]["answer"], 3);
+        assert_eq!(lines[0]["type"], "u32");
+        assert_eq!(lines[1]["answer"], 6);
+        assert_eq!(lines[1]["type"], "i32");
+        assert_eq!(lines[1]["input_hash"], lines[0]["input_hash"]);
+        assert!(lines[1]["elapsed_ns"].is_u64());
+        assert!(lines[1]["parse_ns"].as_u64() <= lines[1]["elapsed_ns"].as_u64());
+    }
+
+    #[test]
+    fn test_tsv() {
+        let runs = [run_input(&Day01, b"L68\nX30", Duration::ZERO, None)];
+        let mut out = Vec::new();
+        write_tsv(&mut out, &runs).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let rows: Vec<Vec<&str>> = out.lines().map(|line| line.split('\t').collect()).collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| row.len() == 9));
+        assert_eq!(rows[1][2], "");
+        assert_eq!(rows[1][6], "false");
+        assert!(rows[1][8].starts_with("day 01, line 2, column 1"));
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    hash::Hasher,
+    io,
+    time::{Duration, Instant},
+};
+
+use fnv::FnvHasher;
+
+use crate::{
+    Answer, ParseError, Part, SOLUTIONS, Solution,
+    cache::Cache,
+    examples::Example,
+    load_input,
+    solution::{Params, Timed},
+    threads,
+};
+
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Result<Answer, ParseError>,
+    /// Time spent parsing the input, included in `elapsed`.
+    pub parse: Duration,
+    pub elapsed: Duration,
+    /// Whether the answer came from the [`Cache`] rather than solving,
+    /// taking no time.
+    pub cached: bool,
+}
+
+impl PartRun {
+    fn new(part: Part, timed: Timed) -> Self {
+        PartRun {
+            part,
+            parse: timed.parse,
+            elapsed: timed.elapsed(),
+            answer: timed.answer,
+            cached: false,
+        }
+    }
+
+    fn cached(part: Part, answer: Answer) -> Self {
+        PartRun {
+            part,
+            answer: Ok(answer),
+            parse: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            cached: true,
+        }
+    }
+
+    /// Time spent solving once the input was parsed.
+    pub fn solve(&self) -> Duration {
+        self.elapsed - self.parse
+    }
+}
+
+pub struct DayRun {
+    pub solution: &'static dyn Solution,
+    /// Time spent reading the input from disk.
+    pub load: Duration,
+    /// [`input_hash`] of the input, if it could be read.
+    pub input_hash: Option<u64>,
+    pub parts: io::Result<Vec<PartRun>>,
+}
+
+impl DayRun {
+    pub fn total(&self) -> Duration {
+        self.load
+            + self
+                .parts
+                .iter()
+                .flatten()
+                .map(|run| run.elapsed)
+                .sum::<Duration>()
+    }
+}
+
+/// FNV-1a hash identifying an input across runs.
+pub fn input_hash(input: &[u8]) -> u64 {
+    let mut hasher = FnvHasher::default();
+    hasher.write(input);
+    hasher.finish()
+}
+
+fn solve_parts(
+    solution: &dyn Solution,
+    input: &[u8],
+    cached: impl Fn(Part) -> Option<Answer>,
+) -> Vec<PartRun> {
+    solution
+        .parts()
+        .iter()
+        .filter_map(|&part| {
+            if let Some(answer) = cached(part) {
+                return Some(PartRun::cached(part, answer));
+            }
+            run_part(solution, part, input)
+        })
+        .collect()
+}
+
+/// Solves and times `part` of `solution` for `input`, if the solution has
+/// that part.
+pub fn run_part(solution: &dyn Solution, part: Part, input: &[u8]) -> Option<PartRun> {
+    let timed = solution.solve_timed(part, input, &Params::new())?;
+    Some(PartRun::new(part, timed))
+}
+
+/// Solves and times every available part of `solution` for `input`.
+pub fn run_parts(solution: &dyn Solution, input: &[u8]) -> Vec<PartRun> {
+    solve_parts(solution, input, |_| None)
+}
+
+/// [`run_parts`], taking the answers `cache` has for `input` instead of
+/// solving them again.
+pub fn run_input(
+    solution: &'static dyn Solution,
+    input: &[u8],
+    load: Duration,
+    cache: Option<&Cache>,
+) -> DayRun {
+    let input_hash = input_hash(input);
+    let parts = solve_parts(solution, input, |part| {
+        cache?.get(solution.day(), part, input_hash)
+    });
+    DayRun {
+        solution,
+        load,
+        input_hash: Some(input_hash),
+        parts: Ok(parts),
+    }
+}
+
+/// Solves and times the parts of `example` that have an expected answer,
+/// with the example's parameters.
+pub fn run_example(solution: &'static dyn Solution, example: &Example) -> io::Result<DayRun> {
+    let start = Instant::now();
+    let input = example.read()?;
+    let load = start.elapsed();
+
+    let parts = solution
+        .parts()
+        .iter()
+        .filter(|&&part| example.expected(part).is_some())
+        .filter_map(|&part| {
+            let timed = solution.solve_timed(part, &input, &example.params)?;
+            Some(PartRun::new(part, timed))
+        })
+        .collect();
+    Ok(DayRun {
+        solution,
+        load,
+        input_hash: Some(input_hash(&input)),
+        parts: Ok(parts),
+    })
+}
+
+pub fn run_day(solution: &'static dyn Solution, cache: Option<&Cache>) -> DayRun {
+    let start = Instant::now();
+    let input = load_input(solution.day());
+    let load = start.elapsed();
+
+    match input {
+        Ok(input) => run_input(solution, &input, load, cache),
+        Err(e) => DayRun {
+            solution,
+            load,
+            input_hash: None,
+            parts: Err(e),
+        },
+    }
+}
+
+/// Runs every registered day, optionally concurrently with [`threads::map`].
+pub fn run_all(parallel: bool, cache: Option<&Cache>) -> Vec<DayRun> {
+    if parallel {
+        threads::map(SOLUTIONS, |&solution| run_day(solution, cache))
+    } else {
+        SOLUTIONS
+            .iter()
+            .map(|&solution| run_day(solution, cache))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::Day01;
+
+    #[test]
+    fn test_run_parts() {
+        let runs = run_parts(&Day01, b"L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82");
+        let answers = runs
+            .into_iter()
+            .map(|run| (run.part, run.answer.unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(answers, [
+            (Part::One, Answer::U32(3)),
+            (Part::Two, Answer::I32(6))
+        ]);
+    }
+}
@@ -0,0 +1,825 @@
+//! Adding a day: the module skeleton, an empty example with its manifest
+//! entry, the registration in `src/lib.rs`, a generator stub in `src/gen.rs`,
+//! its answer test in `tests/answers.rs` and a fuzz target.
+//!
+//! The stubs report an error for each part, and the example tests wait for
+//! the example's answers, so the crate still builds and passes its tests.
+//! Everything goes through the crate root given to [`new_day`], so the same
+//! command sets up the days of a fresh crate laid out like this one.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Io(io::Error),
+    /// The day already has this file or registration.
+    Exists(String),
+    /// The file has no such line to register the day after.
+    Unrecognized {
+        file: &'static str,
+        what: &'static str,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Io(e) => e.fmt(f),
+            ScaffoldError::Exists(what) => write!(f, "{what} already exists"),
+            ScaffoldError::Unrecognized { file, what } => {
+                write!(f, "could not find {what} in {file}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(e: io::Error) -> Self {
+        ScaffoldError::Io(e)
+    }
+}
+
+const MODULE: &str = r#"use crate::{
+    ParseError,
+    solution::{Params, Part, Solution, Timed},
+};
+
+#[cfg(feature = "embedded-inputs")]
+pub const INPUT: &[u8] = include_bytes!("../../inputs/day{NN}.txt");
+
+const DAY: u8 = {N};
+
+pub struct Input<'a> {
+    raw: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        Ok(Input { raw: input })
+    }
+}
+
+/// What the parts report until they are solved.
+fn unsolved(input: &Input, part: Part) -> ParseError {
+    ParseError::new(
+        DAY,
+        input.raw,
+        &input.raw[..0],
+        format!("a solution to part {part}"),
+    )
+}
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    Err(unsolved(input, Part::One))
+}
+
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    Err(unsolved(input, Part::Two))
+}
+
+pub fn try_part_1(input: &[u8]) -> Result<u64, ParseError> {
+    part_1(&Input::parse(input)?)
+}
+
+pub fn try_part_2(input: &[u8]) -> Result<u64, ParseError> {
+    part_2(&Input::parse(input)?)
+}
+
+pub struct Day{NN};
+
+impl Solution for Day{NN} {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "{TITLE}"
+    }
+
+    fn solve_timed(&self, part: Part, input: &[u8], _params: &Params) -> Option<Timed> {
+        Some(match part {
+            Part::One => Timed::measure(input, Input::parse, part_1),
+            Part::Two => Timed::measure(input, Input::parse, part_2),
+        })
+    }
+}
+
+pub fn run(input: &[u8]) {
+    let input = Input::parse(input).unwrap_or_else(|e| panic!("{e}"));
+    println!(
+        "Part 1: {}",
+        part_1(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
+    println!(
+        "Part 2: {}",
+        part_2(&input).unwrap_or_else(|e| panic!("{e}"))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `crate::example_tests!(DAY);` once examples/manifest.toml has the
+    // example's answers.
+    #[test]
+    #[ignore = "the example has no answers yet"]
+    fn test_part_1() {
+        crate::examples::check(DAY, Part::One);
+    }
+
+    #[test]
+    #[ignore = "the example has no answers yet"]
+    fn test_part_2() {
+        crate::examples::check(DAY, Part::Two);
+    }
+}
+"#;
+
+/// Source of the skeleton module for `day`.
+pub fn module(day: u8, title: &str) -> String {
+    MODULE
+        .replace("{NN}", &format!("{day:02}"))
+        .replace("{N}", &day.to_string())
+        .replace("{TITLE}", &title.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Inserts `line` among the lines from `start` on that `key` maps to
+/// `Some`, keeping them sorted, or returns `None` if `key` matches none of
+/// them.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    start: usize,
+    line: String,
+    key: impl Fn(&str) -> Option<u8>,
+) -> Option<()> {
+    let day = key(&line).expect("the new line has a day");
+    let block = lines[start..]
+        .iter()
+        .position(|l| key(l).is_some())
+        .map(|i| start + i)?;
+    let end = lines[block..]
+        .iter()
+        .position(|l| key(l).is_none())
+        .map_or(lines.len(), |i| block + i);
+    let at = (block..end)
+        .find(|&i| key(&lines[i]) > Some(day))
+        .unwrap_or(end);
+    lines.insert(at, line);
+    Some(())
+}
+
+fn parse_day(digits: &str) -> Option<u8> {
+    digits.parse().ok()
+}
+
+/// `lib` with `day` declared, in [`SOLUTIONS`](crate::SOLUTIONS) and in the
+/// embedded inputs of [`load_input`](crate::load_input).
+pub fn register(lib: &str, day: u8) -> Result<String, ScaffoldError> {
+    let declaration = format!("pub mod day{day:02};");
+    if lib.lines().any(|line| line == declaration) {
+        return Err(ScaffoldError::Exists(format!("`{declaration}`")));
+    }
+
+    let mut lines = lib.lines().map(str::to_string).collect::<Vec<_>>();
+    insert_sorted(&mut lines, 0, declaration, |l| {
+        l.strip_prefix("pub mod day")?
+            .strip_suffix(';')
+            .and_then(parse_day)
+    })
+    .ok_or(ScaffoldError::Unrecognized {
+        file: "src/lib.rs",
+        what: "the `pub mod dayNN;` lines",
+    })?;
+
+    let registry = lines
+        .iter()
+        .position(|l| l.starts_with("pub static SOLUTIONS"))
+        .ok_or(ScaffoldError::Unrecognized {
+            file: "src/lib.rs",
+            what: "`SOLUTIONS`",
+        })?;
+    insert_sorted(
+        &mut lines,
+        registry,
+        format!("    &day{day:02}::Day{day:02},"),
+        |l| l.strip_prefix("    &day")?.get(..2).and_then(parse_day),
+    )
+    .ok_or(ScaffoldError::Unrecognized {
+        file: "src/lib.rs",
+        what: "the `SOLUTIONS` entries",
+    })?;
+
+    let embedded = lines
+        .iter()
+        .position(|l| l.trim() == "let embedded = match day {")
+        .ok_or(ScaffoldError::Unrecognized {
+            file: "src/lib.rs",
+            what: "the embedded inputs",
+        })?;
+    insert_sorted(
+        &mut lines,
+        embedded,
+        format!("            {day} => Some(day{day:02}::INPUT),"),
+        |l| {
+            let (day, rest) = l.trim().split_once(" => ")?;
+            rest.ends_with("::INPUT),").then(|| parse_day(day))?
+        },
+    )
+    .ok_or(ScaffoldError::Unrecognized {
+        file: "src/lib.rs",
+        what: "the embedded inputs' match arms",
+    })?;
+
+    let mut lib = lines.join("\n");
+    lib.push('\n');
+    Ok(lib)
+}
+
+const GENERATOR: &str = "\
+fn day{NN}(rng: &mut Rng, size: usize) -> String {
+    // A number a line, until the puzzle's input is known.
+    lines((0..size).map(|_| rng.range(0..=999).to_string()))
+}
+";
+
+/// `generators` (the source of `src/gen.rs`) with a stub generator for
+/// `day` in [`generate`](crate::gen::generate).
+pub fn register_generator(generators: &str, day: u8) -> Result<String, ScaffoldError> {
+    let unrecognized = |what| ScaffoldError::Unrecognized {
+        file: "src/gen.rs",
+        what,
+    };
+    let function = |l: &str| {
+        l.strip_prefix("fn day")?
+            .strip_suffix("(rng: &mut Rng, size: usize) -> String {")
+            .and_then(parse_day)
+    };
+    let mut lines = generators.lines().map(str::to_string).collect::<Vec<_>>();
+    if lines.iter().any(|l| function(l) == Some(day)) {
+        return Err(ScaffoldError::Exists(format!("`fn day{day:02}`")));
+    }
+
+    let registry = lines
+        .iter()
+        .position(|l| l.starts_with("static GENERATORS"))
+        .ok_or(unrecognized("`GENERATORS`"))?;
+    let end = lines[registry..]
+        .iter()
+        .position(|l| l == "];")
+        .map(|i| registry + i)
+        .ok_or(unrecognized("the end of `GENERATORS`"))?;
+    // Entries are `Generator {` followed by their `day: N,` line.
+    let at = (registry..end)
+        .find(|&i| {
+            lines[i] == "    Generator {"
+                && lines
+                    .get(i + 1)
+                    .and_then(|l| l.trim().strip_prefix("day: ")?.strip_suffix(','))
+                    .and_then(parse_day)
+                    > Some(day)
+        })
+        .unwrap_or(end);
+    let entry = [
+        "    Generator {".to_string(),
+        format!("        day: {day},"),
+        "        default_size: 1_000,".to_string(),
+        "        max_size: 16_000,".to_string(),
+        format!("        generate: day{day:02},"),
+        "    },".to_string(),
+    ];
+    lines.splice(at..at, entry);
+
+    let at = lines
+        .iter()
+        .position(|l| function(l) > Some(day) || l == "#[cfg(test)]")
+        .ok_or(unrecognized("the generator functions"))?;
+    let stub = GENERATOR.replace("{NN}", &format!("{day:02}"));
+    lines.splice(
+        at..at,
+        stub.lines().map(str::to_string).chain([String::new()]),
+    );
+
+    let mut generators = lines.join("\n");
+    generators.push('\n');
+    Ok(generators)
+}
+
+/// `answers` (the source of `tests/answers.rs`) with a test for `day` in its
+/// `answer_tests!` list.
+pub fn register_answer_test(answers: &str, day: u8) -> Result<String, ScaffoldError> {
+    let entry = format!("    day{day:02}: {day},");
+    if answers.lines().any(|line| line == entry) {
+        return Err(ScaffoldError::Exists(format!("`{}`", entry.trim())));
+    }
+    let unrecognized = |what| ScaffoldError::Unrecognized {
+        file: "tests/answers.rs",
+        what,
+    };
+
+    let mut lines = answers.lines().map(str::to_string).collect::<Vec<_>>();
+    let list = lines
+        .iter()
+        .position(|l| l == "answer_tests! {")
+        .ok_or(unrecognized("the `answer_tests!` list"))?;
+    insert_sorted(&mut lines, list, entry, |l| {
+        l.strip_prefix("    day")?
+            .split_once(": ")
+            .and_then(|(day, _)| parse_day(day))
+    })
+    .ok_or(unrecognized("the `answer_tests!` entries"))?;
+
+    let mut answers = lines.join("\n");
+    answers.push('\n');
+    Ok(answers)
+}
+
+const FUZZ_TARGET: &str = "\
+#![no_main]
+
+use {CRATE}::day{NN};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let _ = day{NN}::try_part_1(input);
+    let _ = day{NN}::try_part_2(input);
+});
+";
+
+/// `manifest` (the source of `fuzz/Cargo.toml`) with a `[[bin]]` for `day`,
+/// along with the source of that fuzz target.
+pub fn register_fuzz_target(manifest: &str, day: u8) -> Result<(String, String), ScaffoldError> {
+    let unrecognized = |what| ScaffoldError::Unrecognized {
+        file: "fuzz/Cargo.toml",
+        what,
+    };
+    let table = manifest
+        .parse::<toml::Table>()
+        .map_err(|_| unrecognized("valid TOML"))?;
+    // The crate being fuzzed is the dependency on the directory above.
+    let krate = table
+        .get("dependencies")
+        .and_then(toml::Value::as_table)
+        .and_then(|dependencies| {
+            dependencies.iter().find_map(|(name, dependency)| {
+                (dependency.get("path")?.as_str()? == "..").then(|| name.replace('-', "_"))
+            })
+        })
+        .ok_or(unrecognized("a dependency on `..`"))?;
+    let target = |l: &str| {
+        l.strip_prefix("name = \"day")?
+            .strip_suffix('"')
+            .and_then(parse_day)
+    };
+
+    let mut lines = manifest.lines().map(str::to_string).collect::<Vec<_>>();
+    if lines.iter().any(|l| target(l) == Some(day)) {
+        return Err(ScaffoldError::Exists(format!(
+            "the day{day:02} fuzz target"
+        )));
+    }
+    // Before the `[[bin]]` of the next day, or after the last one.
+    let at = lines
+        .iter()
+        .position(|l| target(l) > Some(day))
+        .and_then(|i| lines[..i].iter().rposition(|l| l == "[[bin]]"))
+        .unwrap_or(lines.len());
+    let bin = [
+        "[[bin]]".to_string(),
+        format!("name = \"day{day:02}\""),
+        format!("path = \"fuzz_targets/day{day:02}.rs\""),
+        "test = false".to_string(),
+        "doc = false".to_string(),
+        "bench = false".to_string(),
+    ];
+    if at == lines.len() {
+        lines.push(String::new());
+        lines.extend(bin);
+    } else {
+        lines.splice(at..at, bin.into_iter().chain([String::new()]));
+    }
+
+    let mut manifest = lines.join("\n");
+    manifest.push('\n');
+    let target = FUZZ_TARGET
+        .replace("{CRATE}", &krate)
+        .replace("{NN}", &format!("{day:02}"));
+    Ok((manifest, target))
+}
+
+/// Reads `path`, or `None` if there is no such file.
+fn read_if_exists(path: &Path) -> io::Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes every file in `files`, or if one fails, puts back the ones before
+/// it and removes the directories made for them.
+fn write_all(files: &[(PathBuf, String)]) -> io::Result<()> {
+    let mut undo: Vec<Box<dyn FnOnce()>> = Vec::new();
+    let written = files.iter().try_for_each(|(path, contents)| {
+        let dir = path.parent().expect("files are in a directory");
+        if let Some(created) = dir
+            .ancestors()
+            .take_while(|d| !d.as_os_str().is_empty() && !d.exists())
+            .last()
+        {
+            let created = created.to_path_buf();
+            undo.push(Box::new(move || {
+                let _ = std::fs::remove_dir_all(created);
+            }));
+            std::fs::create_dir_all(dir)?;
+        }
+        let previous = std::fs::read(path);
+        let restored = path.clone();
+        undo.push(Box::new(move || {
+            let _ = match previous {
+                Ok(previous) => std::fs::write(&restored, previous),
+                Err(_) => std::fs::remove_file(&restored),
+            };
+        }));
+        std::fs::write(path, contents)
+    });
+    if written.is_err() {
+        undo.into_iter().rev().for_each(|undo| undo());
+    }
+    written
+}
+
+/// Adds `day` to the crate at `root`, returning the files it created or
+/// changed. Nothing is written if any of them already has the day, and
+/// nothing is left changed if writing one of them fails. A crate without
+/// generators, answer tests or fuzz targets gets none for the day.
+pub fn new_day(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let module_path = root.join(format!("src/day{day:02}/mod.rs"));
+    let example_path = root.join(format!("examples/day{day:02}/example.txt"));
+    let lib_path = root.join("src/lib.rs");
+    let manifest_path = root.join("examples/manifest.toml");
+    let gen_path = root.join("src/gen.rs");
+    let answers_path = root.join("tests/answers.rs");
+    let fuzz_path = root.join("fuzz/Cargo.toml");
+    let fuzz_target_path = root.join(format!("fuzz/fuzz_targets/day{day:02}.rs"));
+
+    for path in [&module_path, &example_path, &fuzz_target_path] {
+        if path.exists() {
+            return Err(ScaffoldError::Exists(path.display().to_string()));
+        }
+    }
+    let mut manifest = read_if_exists(&manifest_path)?.unwrap_or_default();
+    let key = format!("[\"day{day:02}/example.txt\"]");
+    if manifest.lines().any(|line| line == key) {
+        return Err(ScaffoldError::Exists(format!("`{key}` in the manifest")));
+    }
+    if !manifest.is_empty() && !manifest.ends_with("\n\n") {
+        manifest.push('\n');
+    }
+    manifest.push_str(&format!("{key}\n# part1 = \n# part2 = \n"));
+
+    let mut files = vec![
+        (module_path, module(day, title)),
+        (example_path, String::new()),
+        (manifest_path, manifest),
+        (
+            lib_path.clone(),
+            register(&std::fs::read_to_string(&lib_path)?, day)?,
+        ),
+    ];
+    if let Some(generators) = read_if_exists(&gen_path)? {
+        files.push((gen_path, register_generator(&generators, day)?));
+    }
+    if let Some(answers) = read_if_exists(&answers_path)? {
+        files.push((answers_path, register_answer_test(&answers, day)?));
+    }
+    if let Some(fuzz) = read_if_exists(&fuzz_path)? {
+        let (fuzz, target) = register_fuzz_target(&fuzz, day)?;
+        files.push((fuzz_path, fuzz));
+        files.push((fuzz_target_path, target));
+    }
+
+    write_all(&files)?;
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "\
+pub mod day01;
+pub mod day03;
+pub mod error;
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day03::Day03,
+];
+
+pub fn load_input(day: u8) -> io::Result<Cow<'static, [u8]>> {
+        let embedded = match day {
+            1 => Some(day01::INPUT),
+            3 => Some(day03::INPUT),
+            _ => None,
+        };
+}
+";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(LIB, 2).unwrap(),
+            "\
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod error;
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+];
+
+pub fn load_input(day: u8) -> io::Result<Cow<'static, [u8]>> {
+        let embedded = match day {
+            1 => Some(day01::INPUT),
+            2 => Some(day02::INPUT),
+            3 => Some(day03::INPUT),
+            _ => None,
+        };
+}
+"
+        );
+
+        let lib = register(LIB, 12).unwrap();
+        assert!(lib.contains("    &day03::Day03,\n    &day12::Day12,\n];"));
+        assert!(lib.contains("pub mod day12;\npub mod error;"));
+
+        assert!(matches!(register(LIB, 3), Err(ScaffoldError::Exists(_))));
+        assert!(matches!(
+            register("pub mod day01;\n", 2),
+            Err(ScaffoldError::Unrecognized { .. })
+        ));
+    }
+
+    #[test]
+    fn test_registers_the_real_lib() {
+        let lib = include_str!("lib.rs");
+        let registered = register(lib, 25).unwrap();
+        assert!(registered.contains("    &day25::Day25,\n];"));
+        assert!(
+            registered.contains("            25 => Some(day25::INPUT),\n            _ => None,")
+        );
+    }
+
+    const GEN: &str = "\
+static GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        default_size: 4_000,
+        max_size: 64_000,
+        generate: day01,
+    },
+    Generator {
+        day: 3,
+        default_size: 200,
+        max_size: 3_200,
+        generate: day03,
+    },
+];
+
+fn day01(rng: &mut Rng, size: usize) -> String {
+    String::new()
+}
+
+fn day03(rng: &mut Rng, size: usize) -> String {
+    String::new()
+}
+
+#[cfg(test)]
+mod tests {}
+";
+
+    #[test]
+    fn test_register_generator() {
+        let generators = register_generator(GEN, 2).unwrap();
+        assert!(generators.contains(
+            "        generate: day01,
+    },
+    Generator {
+        day: 2,
+        default_size: 1_000,
+        max_size: 16_000,
+        generate: day02,
+    },
+    Generator {
+        day: 3,"
+        ));
+        assert!(generators.contains(
+            "}
+
+fn day02(rng: &mut Rng, size: usize) -> String {
+    // A number a line, until the puzzle's input is known.
+    lines((0..size).map(|_| rng.range(0..=999).to_string()))
+}
+
+fn day03("
+        ));
+
+        let generators = register_generator(GEN, 12).unwrap();
+        assert!(generators.contains("        generate: day12,\n    },\n];"));
+        assert!(generators.contains("to_string()))\n}\n\n#[cfg(test)]"));
+
+        assert!(matches!(
+            register_generator(GEN, 3),
+            Err(ScaffoldError::Exists(_))
+        ));
+        assert!(matches!(
+            register_generator("fn day01() {}\n", 2),
+            Err(ScaffoldError::Unrecognized { .. })
+        ));
+    }
+
+    #[test]
+    fn test_registers_the_real_generators() {
+        let generators = register_generator(include_str!("gen.rs"), 25).unwrap();
+        assert!(generators.contains("        generate: day25,\n    },\n];"));
+        assert!(generators.contains("fn day25(rng: &mut Rng, size: usize) -> String {"));
+    }
+
+    const ANSWERS: &str = "\
+macro_rules! answer_tests {
+    ($($name:ident: $day:literal,)*) => {};
+}
+
+answer_tests! {
+    day01: 1,
+    day03: 3,
+}
+";
+
+    #[test]
+    fn test_register_answer_test() {
+        let answers = register_answer_test(ANSWERS, 2).unwrap();
+        assert!(answers.contains("    day01: 1,\n    day02: 2,\n    day03: 3,\n}"));
+        let answers = register_answer_test(ANSWERS, 12).unwrap();
+        assert!(answers.contains("    day03: 3,\n    day12: 12,\n}"));
+
+        assert!(matches!(
+            register_answer_test(ANSWERS, 3),
+            Err(ScaffoldError::Exists(_))
+        ));
+        assert!(matches!(
+            register_answer_test("fn check_day(day: u8) {}\n", 2),
+            Err(ScaffoldError::Unrecognized { .. })
+        ));
+    }
+
+    #[test]
+    fn test_registers_the_real_answer_tests() {
+        let answers = register_answer_test(include_str!("../tests/answers.rs"), 25).unwrap();
+        assert!(answers.contains("    day25: 25,\n}"));
+    }
+
+    const FUZZ: &str = r#"[package]
+name = "aoc-2025-fuzz"
+
+[dependencies]
+aoc-2025 = { path = ".." }
+libfuzzer-sys = "0.4.12"
+
+[[bin]]
+name = "day01"
+path = "fuzz_targets/day01.rs"
+test = false
+doc = false
+bench = false
+
+[[bin]]
+name = "day03"
+path = "fuzz_targets/day03.rs"
+test = false
+doc = false
+bench = false
+"#;
+
+    #[test]
+    fn test_register_fuzz_target() {
+        let (manifest, target) = register_fuzz_target(FUZZ, 2).unwrap();
+        assert!(manifest.contains(
+            r#"bench = false
+
+[[bin]]
+name = "day02"
+path = "fuzz_targets/day02.rs"
+test = false
+doc = false
+bench = false
+
+[[bin]]
+name = "day03""#
+        ));
+        assert!(target.contains("use aoc_2025::day02;"));
+        assert!(target.contains("day02::try_part_2(input);"));
+        let (manifest, _) = register_fuzz_target(FUZZ, 12).unwrap();
+        assert!(manifest.ends_with(
+            "name = \"day12\"\npath = \"fuzz_targets/day12.rs\"\ntest = false\ndoc = false\nbench \
+             = false\n"
+        ));
+        assert!(manifest.parse::<toml::Table>().is_ok());
+
+        assert!(matches!(
+            register_fuzz_target(FUZZ, 3),
+            Err(ScaffoldError::Exists(_))
+        ));
+        assert!(matches!(
+            register_fuzz_target("[package]\n", 2),
+            Err(ScaffoldError::Unrecognized { .. })
+        ));
+    }
+
+    #[test]
+    fn test_registers_the_real_fuzz_targets() {
+        let (manifest, _) = register_fuzz_target(include_str!("../fuzz/Cargo.toml"), 25).unwrap();
+        assert!(manifest.ends_with(
+            "name = \"day25\"\npath = \"fuzz_targets/day25.rs\"\ntest = false\ndoc = false\nbench \
+             = false\n"
+        ));
+    }
+
+    /// A crate root with the files [`new_day`] changes.
+    fn crate_root() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        for (path, contents) in [
+            ("src/lib.rs", LIB),
+            ("src/gen.rs", GEN),
+            ("tests/answers.rs", ANSWERS),
+            ("fuzz/Cargo.toml", FUZZ),
+        ] {
+            let path = root.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = crate_root();
+
+        let written = new_day(root.path(), 2, "Gift \"Shop\"").unwrap();
+        assert_eq!(written.len(), 8);
+        let module = std::fs::read_to_string(root.path().join("src/day02/mod.rs")).unwrap();
+        assert!(module.contains("pub fn run(input: &[u8]) {"));
+        assert!(module.contains("pub struct Day02;"));
+        assert!(module.contains("const DAY: u8 = 2;"));
+        assert!(module.contains(r#""Gift \"Shop\"""#));
+        let manifest = std::fs::read_to_string(root.path().join("examples/manifest.toml")).unwrap();
+        assert_eq!(crate::examples::parse(&manifest).unwrap()[0].day, 2);
+        let generators = std::fs::read_to_string(root.path().join("src/gen.rs")).unwrap();
+        assert!(generators.contains("generate: day02,"));
+        let answers = std::fs::read_to_string(root.path().join("tests/answers.rs")).unwrap();
+        assert!(answers.contains("    day02: 2,"));
+        assert!(root.path().join("fuzz/fuzz_targets/day02.rs").exists());
+
+        let lib = std::fs::read_to_string(root.path().join("src/lib.rs")).unwrap();
+        assert!(matches!(
+            new_day(root.path(), 2, "Again"),
+            Err(ScaffoldError::Exists(_))
+        ));
+        assert_eq!(
+            std::fs::read_to_string(root.path().join("src/lib.rs")).unwrap(),
+            lib
+        );
+    }
+
+    #[test]
+    fn test_new_day_rolls_back() {
+        let root = crate_root();
+        // The fuzz target is written last, and cannot be with a file in the
+        // way of its directory.
+        std::fs::write(root.path().join("fuzz/fuzz_targets"), "").unwrap();
+
+        assert!(matches!(
+            new_day(root.path(), 2, "Gift Shop"),
+            Err(ScaffoldError::Io(_))
+        ));
+        assert_eq!(
+            std::fs::read_to_string(root.path().join("src/lib.rs")).unwrap(),
+            LIB
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.path().join("fuzz/Cargo.toml")).unwrap(),
+            FUZZ
+        );
+        assert!(!root.path().join("src/day02").exists());
+        assert!(!root.path().join("examples").exists());
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    time::{Duration, Instant},
+};
+
+use crate::{ParseError, repl::Explore, stream::StreamError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: &'static [Part] = &[Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(n: u8) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    I32(i32),
+    U32(u32),
+    U64(u64),
+    Usize(usize),
+}
+
+impl Answer {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Answer::I32(_) => "i32",
+            Answer::U32(_) => "u32",
+            Answer::U64(_) => "u64",
+            Answer::Usize(_) => "usize",
+        }
+    }
+
+    /// The answer of type `type_name` written as `s`, as in
+    /// [`type_name`](Self::type_name) and [`Display`](fmt::Display).
+    pub fn parse(type_name: &str, s: &str) -> Option<Answer> {
+        match type_name {
+            "i32" => s.parse().ok().map(Answer::I32),
+            "u32" => s.parse().ok().map(Answer::U32),
+            "u64" => s.parse().ok().map(Answer::U64),
+            "usize" => s.parse().ok().map(Answer::Usize),
+            _ => None,
+        }
+    }
+}
+
+impl serde::Serialize for Answer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Answer::I32(n) => serializer.serialize_i32(n),
+            Answer::U32(n) => serializer.serialize_u32(n),
+            Answer::U64(n) => serializer.serialize_u64(n),
+            Answer::Usize(n) => serializer.serialize_u64(n as u64),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::I32(n) => n.fmt(f),
+            Answer::U32(n) => n.fmt(f),
+            Answer::U64(n) => n.fmt(f),
+            Answer::Usize(n) => n.fmt(f),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::I32(n)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::U32(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::U64(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Usize(n)
+    }
+}
+
+pub trait Solution: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn parts(&self) -> &'static [Part] {
+        Part::BOTH
+    }
+
+    /// Solves `part` for `input`, or `None` if the day has no such part.
+    fn solve(&self, part: Part, input: &[u8]) -> Option<Result<Answer, ParseError>> {
+        self.solve_with(part, input, &Params::new())
+    }
+
+    /// [`solve`](Self::solve) with the puzzle's constants overridden by
+    /// `params`, as its examples need.
+    fn solve_with(
+        &self,
+        part: Part,
+        input: &[u8],
+        params: &Params,
+    ) -> Option<Result<Answer, ParseError>> {
+        self.solve_timed(part, input, params)
+            .map(|timed| timed.answer)
+    }
+
+    /// [`solve_with`](Self::solve_with), timing the parsing apart from the
+    /// solving.
+    fn solve_timed(&self, part: Part, input: &[u8], params: &Params) -> Option<Timed>;
+
+    /// Every part of the input in `reader`, solved in one pass and bounded
+    /// memory, or `None` if the day needs its whole input at once. A part can
+    /// fail on its own without failing the others.
+    fn solve_reader(&self, reader: &mut dyn io::BufRead) -> Option<Result<Answers, StreamError>> {
+        let _ = reader;
+        None
+    }
+
+    /// The parsed `input` with the [`repl`](crate::repl) commands to look
+    /// into it, or `None` if the day offers none.
+    fn explore<'a>(&self, input: &'a [u8]) -> Option<Result<Box<dyn Explore + 'a>, ParseError>> {
+        let _ = input;
+        None
+    }
+}
+
+/// An answer with how long parsing the input and then solving it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timed {
+    pub answer: Result<Answer, ParseError>,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timed {
+    /// Runs `parse` on `input`, then `solve` on what it parsed.
+    pub fn measure<'a, I, T: Into<Answer>>(
+        input: &'a [u8],
+        parse: impl FnOnce(&'a [u8]) -> Result<I, ParseError>,
+        solve: impl FnOnce(&I) -> Result<T, ParseError>,
+    ) -> Timed {
+        let start = Instant::now();
+        let parsed = parse(input);
+        let parse = start.elapsed();
+        let start = Instant::now();
+        let answer = parsed.and_then(|parsed| solve(&parsed)).map(Into::into);
+        Timed {
+            answer,
+            parse,
+            solve: start.elapsed(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// Each part's answer to a streamed input, or why that part failed.
+pub type Answers = Vec<(Part, Result<Answer, ParseError>)>;
+
+/// Constants the puzzle states outside its input, such as how many steps to
+/// take, by name.
+pub type Params = BTreeMap<String, usize>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownDay(pub u8);
+
+impl fmt::Display for UnknownDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} is not implemented; available days: ", self.0)?;
+        for (i, solution) in crate::SOLUTIONS.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", solution.day())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownDay {}
@@ -0,0 +1,184 @@
+//! Solving inputs too large to hold in memory, for the days whose lines are
+//! independent of each other.
+//!
+//! The input is read in chunks of whole lines, a batch of chunks per thread
+//! at a time. Each chunk is parsed with [`threads::map`], then the results
+//! are folded in input order, so a batch is all that is ever in memory.
+
+use std::{fmt, io};
+
+use crate::{ParseError, threads};
+
+/// Bytes per chunk, give or take the rest of the last line.
+pub const CHUNK_SIZE: usize = 1 << 20;
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => e.fmt(f),
+            StreamError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> Self {
+        StreamError::Parse(e)
+    }
+}
+
+/// Whole lines of the input, starting on line `first_line`.
+pub struct Chunk {
+    first_line: usize,
+    bytes: Vec<u8>,
+}
+
+impl Chunk {
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// `e`, located in the chunk, moved to its line in the whole input.
+    pub fn locate(&self, mut e: ParseError) -> ParseError {
+        e.line += self.first_line - 1;
+        e
+    }
+}
+
+/// Reads lines into `chunk` until it holds at least `size` bytes or the
+/// input ends.
+fn read_chunk(reader: &mut impl io::BufRead, size: usize, chunk: &mut Vec<u8>) -> io::Result<()> {
+    while chunk.len() < size {
+        if reader.read_until(b'\n', chunk)? == 0 {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Maps every chunk of whole lines of `reader` with `map`, in parallel, and
+/// folds the results in order with `fold`, which also gets the chunk. Errors
+/// from either are located in the chunk and moved to their line in the whole
+/// input.
+pub fn try_fold<B, T: Send>(
+    reader: impl io::BufRead,
+    init: B,
+    map: impl Fn(&[u8]) -> Result<T, ParseError> + Sync + Send,
+    fold: impl FnMut(B, &Chunk, T) -> Result<B, ParseError>,
+) -> Result<B, StreamError> {
+    try_fold_sized(reader, CHUNK_SIZE, 1, init, map, fold)
+}
+
+/// [`try_fold`] on the rest of an input whose first lines were already read
+/// from `reader`, so that it starts on line `first_line`.
+pub fn try_fold_from<B, T: Send>(
+    reader: impl io::BufRead,
+    first_line: usize,
+    init: B,
+    map: impl Fn(&[u8]) -> Result<T, ParseError> + Sync + Send,
+    fold: impl FnMut(B, &Chunk, T) -> Result<B, ParseError>,
+) -> Result<B, StreamError> {
+    try_fold_sized(reader, CHUNK_SIZE, first_line, init, map, fold)
+}
+
+fn try_fold_sized<B, T: Send>(
+    mut reader: impl io::BufRead,
+    chunk_size: usize,
+    mut next_line: usize,
+    mut acc: B,
+    map: impl Fn(&[u8]) -> Result<T, ParseError> + Sync + Send,
+    mut fold: impl FnMut(B, &Chunk, T) -> Result<B, ParseError>,
+) -> Result<B, StreamError> {
+    let batch_size = if threads::is_sequential() {
+        1
+    } else {
+        rayon::current_num_threads()
+    };
+
+    loop {
+        let mut batch = Vec::with_capacity(batch_size);
+        while batch.len() < batch_size {
+            let mut bytes = Vec::with_capacity(chunk_size);
+            read_chunk(&mut reader, chunk_size, &mut bytes)?;
+            if bytes.is_empty() {
+                break;
+            }
+            let first_line = next_line;
+            next_line += bytes.iter().filter(|&&c| c == b'\n').count();
+            batch.push(Chunk { first_line, bytes });
+        }
+        if batch.is_empty() {
+            return Ok(acc);
+        }
+
+        let results = threads::map(&batch, |chunk| map(&chunk.bytes));
+        for (chunk, result) in batch.iter().zip(results) {
+            let value = result.map_err(|e| chunk.locate(e))?;
+            acc = fold(acc, chunk, value).map_err(|e| chunk.locate(e))?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::ByteSlice;
+
+    use super::*;
+    use crate::threads::{Threads, run};
+
+    fn sum_lines(input: &[u8], chunk_size: usize) -> Result<(u64, usize), StreamError> {
+        try_fold_sized(
+            input,
+            chunk_size,
+            1,
+            (0, 0),
+            |chunk| {
+                chunk
+                    .lines()
+                    .map(|line| crate::parse::expect_uint::<u64>(0, chunk, line, "a number"))
+                    .sum::<Result<u64, _>>()
+            },
+            |(total, chunks), _, sum| Ok((total + sum, chunks + 1)),
+        )
+    }
+
+    #[test]
+    fn test_try_fold() {
+        let input = (1..=100)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        for threads in [Threads::Global, Threads::Sequential] {
+            let (total, chunks) = run(threads, || sum_lines(input.as_bytes(), 16)).unwrap();
+            assert_eq!(total, 5050);
+            assert!(chunks > 10, "{chunks}");
+        }
+        assert_eq!(sum_lines(input.as_bytes(), CHUNK_SIZE).unwrap(), (5050, 1));
+        assert_eq!(sum_lines(b"", 16).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn test_error_line() {
+        let mut input = "1\n".repeat(40);
+        input.push_str("1\n22x\n1\n");
+        let Err(StreamError::Parse(e)) = sum_lines(input.as_bytes(), 5) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((e.line, e.column), (42, 1));
+        assert_eq!(e.found.as_deref(), Some("22x"));
+    }
+}
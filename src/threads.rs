@@ -0,0 +1,93 @@
+//! How the solutions spread their work over threads.
+//!
+//! Solutions hand their parallel loops to [`map`], which runs them on the
+//! current rayon pool, or on the calling thread in [`Threads::Sequential`]
+//! mode. Either way the results come back in input order.
+
+use std::cell::Cell;
+
+use rayon::prelude::*;
+
+/// The threads to [`run`] a computation on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Threads {
+    /// Rayon's global pool, one thread per CPU.
+    #[default]
+    Global,
+    /// A dedicated pool with this many threads.
+    Pool(usize),
+    /// The calling thread only, without touching rayon.
+    Sequential,
+}
+
+thread_local! {
+    static SEQUENTIAL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether [`map`] runs on the calling thread.
+pub fn is_sequential() -> bool {
+    SEQUENTIAL.get()
+}
+
+/// Runs `f` with every [`map`] inside it spread over `threads`.
+///
+/// # Panics
+///
+/// If rayon cannot start the threads for [`Threads::Pool`].
+pub fn run<R: Send>(threads: Threads, f: impl FnOnce() -> R + Send) -> R {
+    match threads {
+        Threads::Global => f(),
+        Threads::Pool(n) => rayon::ThreadPoolBuilder::new()
+            .num_threads(n)
+            .build()
+            .expect("failed to start the thread pool")
+            .install(f),
+        Threads::Sequential => {
+            struct Restore(bool);
+            impl Drop for Restore {
+                fn drop(&mut self) {
+                    SEQUENTIAL.set(self.0);
+                }
+            }
+
+            let _restore = Restore(SEQUENTIAL.replace(true));
+            f()
+        }
+    }
+}
+
+/// `items.map(f)` as a `Vec`, computed in parallel unless [`is_sequential`].
+pub fn map<T: Send, U: Send>(
+    items: impl IntoIterator<Item = T>,
+    f: impl Fn(T) -> U + Sync + Send,
+) -> Vec<U> {
+    let items = items.into_iter().collect::<Vec<_>>();
+    if is_sequential() {
+        items.into_iter().map(f).collect()
+    } else {
+        items.into_par_iter().map(f).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let squares = (0..100u64).map(|i| i * i).collect::<Vec<_>>();
+        for threads in [Threads::Global, Threads::Pool(3), Threads::Sequential] {
+            assert_eq!(run(threads, || map(0..100u64, |i| i * i)), squares);
+        }
+    }
+
+    #[test]
+    fn test_sequential_stays_on_the_calling_thread() {
+        let caller = std::thread::current().id();
+        let ids = run(Threads::Sequential, || {
+            map(0..64, |_| std::thread::current().id())
+        });
+        assert!(ids.iter().all(|&id| id == caller));
+        assert!(!is_sequential());
+    }
+}
@@ -0,0 +1,127 @@
+//! Waiting for input files to change, through Linux inotify.
+
+use std::{
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use inotify::{Inotify, WatchDescriptor, WatchMask};
+
+/// How long the files must stay untouched after a change before
+/// [`Watcher::wait`] returns, so that a save in several steps is one change.
+const QUIET: Duration = Duration::from_millis(50);
+
+pub struct Watcher {
+    inotify: Inotify,
+    /// Each watched directory, with the one file in it that matters or
+    /// `None` for all of them.
+    watches: Vec<(WatchDescriptor, Option<OsString>)>,
+    buffer: Vec<u8>,
+}
+
+impl Watcher {
+    /// Watches `paths`, which may be files or directories. Files are watched
+    /// through their directory, since editors often save by replacing them.
+    pub fn new(paths: &[PathBuf]) -> io::Result<Self> {
+        let inotify = Inotify::init()?;
+        let mut watches = Vec::new();
+        for path in paths {
+            let (dir, file) = if path.is_dir() {
+                (path.as_path(), None)
+            } else {
+                let dir = path
+                    .parent()
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .unwrap_or(Path::new("."));
+                (dir, path.file_name().map(OsString::from))
+            };
+            let wd = inotify
+                .watches()
+                .add(dir, WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO)?;
+            watches.push((wd, file));
+        }
+        Ok(Watcher {
+            inotify,
+            watches,
+            buffer: vec![0; 4096],
+        })
+    }
+
+    /// Blocks until a watched file is written or replaced.
+    pub fn wait(&mut self) -> io::Result<()> {
+        loop {
+            let events = self.inotify.read_events_blocking(&mut self.buffer)?;
+            let changed = events.into_iter().any(|event| {
+                self.watches.iter().any(|(wd, file)| {
+                    *wd == event.wd && file.as_deref().is_none_or(|file| event.name == Some(file))
+                })
+            });
+            if changed {
+                break;
+            }
+        }
+
+        loop {
+            thread::sleep(QUIET);
+            match self.inotify.read_events(&mut self.buffer) {
+                Ok(_) => continue,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// How `after` compares to `before`, such as `+1.20ms (+15%)`.
+pub fn change(before: Duration, after: Duration) -> String {
+    let (sign, diff) = if after >= before {
+        ('+', after - before)
+    } else {
+        ('-', before - after)
+    };
+    if before.is_zero() {
+        return format!("{sign}{diff:.2?}");
+    }
+    let percent = (diff.as_secs_f64() / before.as_secs_f64() * 100.0).round();
+    format!("{sign}{diff:.2?} ({sign}{percent}%)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change() {
+        let ms = Duration::from_millis;
+        assert_eq!(change(ms(10), ms(12)), "+2.00ms (+20%)");
+        assert_eq!(change(ms(10), ms(5)), "-5.00ms (-50%)");
+        assert_eq!(change(ms(10), ms(10)), "+0.00ns (+0%)");
+        assert_eq!(change(ms(0), ms(1)), "+1.00ms");
+    }
+
+    #[test]
+    fn test_wait() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("day01.txt");
+        std::fs::write(&input, "L1").unwrap();
+        let mut watcher = Watcher::new(std::slice::from_ref(&input)).unwrap();
+
+        let writer = thread::spawn({
+            let dir = dir.path().to_path_buf();
+            move || {
+                thread::sleep(Duration::from_millis(20));
+                std::fs::write(dir.join("other.txt"), "ignored").unwrap();
+                thread::sleep(Duration::from_millis(20));
+                // Saved the way many editors do, by renaming a new file.
+                std::fs::write(dir.join("day01.txt.tmp"), "L2").unwrap();
+                std::fs::rename(dir.join("day01.txt.tmp"), dir.join("day01.txt")).unwrap();
+            }
+        });
+        watcher.wait().unwrap();
+        assert_eq!(std::fs::read_to_string(&input).unwrap(), "L2");
+        writer.join().unwrap();
+    }
+}
@@ -0,0 +1,62 @@
+//! Checks every day against `answers.toml`, skipping days without a local
+//! input or expected answer.
+
+use aoc_2025::{
+    answers::{self, Answers},
+    input_path, load_input, runner,
+};
+
+fn check_day(day: u8) {
+    let solution = aoc_2025::solution(day).unwrap();
+    let Ok(input) = load_input(day) else {
+        eprintln!(
+            "skipping day {day:02}: no input at {}",
+            input_path(day).display()
+        );
+        return;
+    };
+    let answers = Answers::load(&answers::answers_path()).unwrap();
+
+    for run in runner::run_parts(solution, &input) {
+        let Some(expected) = answers.get(day, run.part) else {
+            eprintln!(
+                "skipping day {day:02} part {}: no expected answer",
+                run.part
+            );
+            continue;
+        };
+        let answer = run.answer.unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(
+            answer.to_string(),
+            expected,
+            "day {day:02} part {}",
+            run.part
+        );
+    }
+}
+
+macro_rules! answer_tests {
+    ($($name:ident: $day:literal,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_day($day);
+            }
+        )*
+    };
+}
+
+answer_tests! {
+    day01: 1,
+    day02: 2,
+    day03: 3,
+    day04: 4,
+    day05: 5,
+    day06: 6,
+    day07: 7,
+    day08: 8,
+    day09: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+}
@@ -0,0 +1,449 @@
+//! Random valid inputs must get the same answers from the fast solutions and
+//! the naive ones in each day's `reference` module.
+
+use aoc_2025::*;
+use itertools::Itertools;
+use proptest::{collection::vec, prelude::*, sample::subsequence};
+
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().join("\n")
+}
+
+fn day01_input() -> impl Strategy<Value = String> {
+    vec((any::<bool>(), 0u32..400), 1..30).prop_map(|moves| {
+        lines(
+            moves
+                .into_iter()
+                .map(|(left, distance)| format!("{}{distance}", if left { 'L' } else { 'R' })),
+        )
+    })
+}
+
+fn day02_input() -> impl Strategy<Value = String> {
+    let start = prop_oneof![1u64..1_000, 1u64..10_000_000];
+    vec((start, 0u64..300), 1..6).prop_map(|ranges| {
+        ranges
+            .into_iter()
+            .map(|(start, len)| format!("{start}-{}", start + len))
+            .join(",")
+    })
+}
+
+fn day03_input() -> impl Strategy<Value = String> {
+    vec(vec(1u8..=9, 12..=16), 1..5)
+        .prop_map(|banks| lines(banks.into_iter().map(|bank| bank.into_iter().join(""))))
+}
+
+fn day04_input() -> impl Strategy<Value = String> {
+    (1usize..10, 1usize..10).prop_flat_map(|(width, height)| {
+        vec(vec(any::<bool>(), width), height).prop_map(|rows| {
+            lines(rows.into_iter().map(|row| {
+                row.into_iter()
+                    .map(|roll| if roll { '@' } else { '.' })
+                    .collect()
+            }))
+        })
+    })
+}
+
+fn day05_input() -> impl Strategy<Value = String> {
+    (vec((0u64..300, 0u64..60), 1..8), vec(0u64..400, 0..8)).prop_map(|(ranges, ids)| {
+        let ranges = lines(
+            ranges
+                .into_iter()
+                .map(|(start, len)| format!("{start}-{}", start + len)),
+        );
+        format!(
+            "{ranges}\n\n{}",
+            lines(ids.into_iter().map(|id| id.to_string()))
+        )
+    })
+}
+
+fn day06_input() -> impl Strategy<Value = String> {
+    // Every problem has one number per row, each aligned left or right.
+    let problem = |rows| (any::<bool>(), vec((1u64..10_000, any::<bool>()), rows));
+    (1usize..=4)
+        .prop_flat_map(move |rows| (Just(rows), vec(problem(rows), 1..5)))
+        .prop_map(|(rows, problems)| {
+            let widths = problems
+                .iter()
+                .map(|(_, numbers)| {
+                    numbers
+                        .iter()
+                        .map(|(n, _)| n.to_string().len())
+                        .max()
+                        .unwrap()
+                })
+                .collect_vec();
+            let mut sheet = (0..rows)
+                .map(|row| {
+                    problems
+                        .iter()
+                        .zip(&widths)
+                        .map(|((_, numbers), &width)| match numbers[row] {
+                            (n, true) => format!("{n:<width$}"),
+                            (n, false) => format!("{n:>width$}"),
+                        })
+                        .join(" ")
+                })
+                .collect_vec();
+            sheet.push(
+                problems
+                    .iter()
+                    .zip(&widths)
+                    .map(|((multiply, _), &width)| {
+                        format!("{:<width$}", if *multiply { '*' } else { '+' })
+                    })
+                    .join(" "),
+            );
+            lines(sheet)
+        })
+}
+
+fn day07_input() -> impl Strategy<Value = String> {
+    (3usize..12, 1usize..10).prop_flat_map(|(width, height)| {
+        let splitters = vec(vec(prop::bool::weighted(0.3), width - 2), height);
+        (0..width, splitters).prop_map(move |(start, rows)| {
+            let first = (0..width)
+                .map(|x| if x == start { 'S' } else { '.' })
+                .collect::<String>();
+            let rows = rows.into_iter().map(|row| {
+                format!(
+                    ".{}.",
+                    row.into_iter()
+                        .map(|splitter| if splitter { '^' } else { '.' })
+                        .collect::<String>()
+                )
+            });
+            lines(std::iter::once(first).chain(rows))
+        })
+    })
+}
+
+fn day08_input() -> impl Strategy<Value = (String, usize)> {
+    vec((0u64..40, 0u64..40, 0u64..40), 2..12)
+        .prop_filter("boxes are distinct", |boxes| boxes.iter().all_unique())
+        .prop_flat_map(|boxes| {
+            let pairs = boxes.len() * (boxes.len() - 1) / 2;
+            let input = lines(boxes.into_iter().map(|(x, y, z)| format!("{x},{y},{z}")));
+            (Just(input), 1..=pairs)
+        })
+}
+
+fn day09_input() -> impl Strategy<Value = String> {
+    // A loop of red tiles around columns of floor that each span some rows
+    // and overlap their neighbours, walked along the top and back along the
+    // bottom. As in the puzzle inputs, parallel edges are never on
+    // neighbouring rows or columns: `day09` treats the loop as a polygon, which
+    // has room between such edges where no tile fits.
+    let column = (2u64..5, 0u64..8, 2u64..8);
+    vec(column, 1..5)
+        .prop_filter("neighbouring columns overlap", |columns| {
+            columns
+                .iter()
+                .tuple_windows()
+                .all(|(&(_, b1, h1), &(_, b2, h2))| {
+                    let (t1, t2) = (b1 + h1, b2 + h2);
+                    b1.abs_diff(b2) >= 2 && t1.abs_diff(t2) >= 2 && b1.max(b2) + 2 <= t1.min(t2)
+                })
+        })
+        .prop_map(|columns| {
+            let mut x = 0;
+            let mut top = Vec::new();
+            let mut bottom = Vec::new();
+            for (width, y, height) in columns {
+                top.extend([(x, y + height), (x + width, y + height)]);
+                bottom.extend([(x, y), (x + width, y)]);
+                x += width;
+            }
+            bottom.reverse();
+            lines(
+                top.into_iter()
+                    .chain(bottom)
+                    .map(|(x, y)| format!("{x},{y}")),
+            )
+        })
+}
+
+fn day10_input() -> impl Strategy<Value = String> {
+    let machine = (1usize..=5).prop_flat_map(|lights| {
+        vec(subsequence((0..lights).collect_vec(), 1..=lights), 1..=4).prop_flat_map(
+            move |buttons| {
+                let n = buttons.len();
+                (Just(buttons), vec(any::<bool>(), n), vec(0u64..=2, n)).prop_map(
+                    move |(buttons, toggled, presses)| {
+                        let mut target = vec!['.'; lights];
+                        let mut joltage = vec![0; lights];
+                        for (button, (toggled, presses)) in
+                            buttons.iter().zip(toggled.into_iter().zip(presses))
+                        {
+                            for &light in button {
+                                if toggled {
+                                    target[light] = if target[light] == '#' { '.' } else { '#' };
+                                }
+                                joltage[light] += presses;
+                            }
+                        }
+                        format!(
+                            "[{}] {} {{{}}}",
+                            target.into_iter().collect::<String>(),
+                            buttons
+                                .iter()
+                                .map(|button| format!("({})", button.iter().join(",")))
+                                .join(" "),
+                            joltage.iter().join(","),
+                        )
+                    },
+                )
+            },
+        )
+    });
+    vec(machine, 1..4).prop_map(lines)
+}
+
+fn day11_input() -> impl Strategy<Value = String> {
+    // Devices are only ever wired to devices later in the list, so there are
+    // no cycles.
+    (0usize..6)
+        .prop_flat_map(|extra| {
+            let middle = ["dac", "fft"]
+                .into_iter()
+                .map(String::from)
+                .chain((0..extra).map(|i| format!("d{i:02}")))
+                .collect_vec();
+            let n = middle.len() + 3;
+            (
+                Just(middle).prop_shuffle(),
+                vec(vec(prop::bool::weighted(0.4), n), n),
+            )
+        })
+        .prop_map(|(middle, wires)| {
+            let devices = ["svr", "you"]
+                .into_iter()
+                .map(String::from)
+                .chain(middle)
+                .chain(["out".to_string()])
+                .collect_vec();
+            let last = devices.len() - 1;
+            lines((0..last).map(|i| {
+                let mut outputs = (i + 1..=last).filter(|&j| wires[i][j]).collect_vec();
+                if outputs.is_empty() {
+                    outputs.push(last);
+                }
+                format!(
+                    "{}: {}",
+                    devices[i],
+                    outputs.into_iter().map(|j| &devices[j]).join(" ")
+                )
+            }))
+        })
+}
+
+fn day12_input() -> impl Strategy<Value = String> {
+    // As in the puzzle inputs, every region either has a 3x3 square for each
+    // of its presents or fewer tiles than they cover, which is what
+    // `day12::part_1` relies on. The reference still searches for a packing.
+    let shape = subsequence((0..9).collect_vec(), 5..=8);
+    vec(shape, 1..=4).prop_flat_map(|shapes| {
+        let shape_count = shapes.len();
+        let region = (3usize..12, 3usize..12, any::<bool>())
+            .prop_flat_map(move |(w, h, fits)| {
+                let presents = if fits {
+                    0..=(w / 3) * (h / 3)
+                } else {
+                    w * h / 5 + 1..=w * h / 5 + 3
+                };
+                let picks = presents.prop_flat_map(move |n| vec(0..shape_count, n));
+                (Just(w), Just(h), picks)
+            })
+            .prop_map(move |(w, h, picks)| {
+                let mut counts = vec![0; shape_count];
+                picks.into_iter().for_each(|shape| counts[shape] += 1);
+                (w, h, counts)
+            });
+        (Just(shapes), vec(region, 1..5)).prop_map(|(shapes, regions)| {
+            let shapes = shapes
+                .into_iter()
+                .enumerate()
+                .map(|(i, tiles)| {
+                    let rows = (0..3).map(|y| {
+                        (0..3)
+                            .map(|x| {
+                                if tiles.contains(&(y * 3 + x)) {
+                                    '#'
+                                } else {
+                                    '.'
+                                }
+                            })
+                            .collect::<String>()
+                    });
+                    format!("{i}:\n{}", lines(rows))
+                })
+                .collect_vec();
+            let regions = regions
+                .into_iter()
+                .map(|(w, h, counts)| format!("{w}x{h}: {}", counts.iter().join(" ")));
+            format!("{}\n\n{}", shapes.join("\n\n"), lines(regions))
+        })
+    })
+}
+
+proptest! {
+    #[test]
+    fn day01(input in day01_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day01::try_part_1(input).unwrap(),
+            day01::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day01::try_part_2(input).unwrap(),
+            day01::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day02(input in day02_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day02::try_part_1(input).unwrap(),
+            day02::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day02::try_part_2(input).unwrap(),
+            day02::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day03(input in day03_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day03::try_part_1(input).unwrap(),
+            day03::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day03::try_part_2(input).unwrap(),
+            day03::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day04(input in day04_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day04::try_part_1(input).unwrap(),
+            day04::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day04::try_part_2(input).unwrap(),
+            day04::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day05(input in day05_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day05::try_part_1(input).unwrap(),
+            day05::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day05::try_part_2(input).unwrap(),
+            day05::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day06(input in day06_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day06::try_part_1(input).unwrap(),
+            day06::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day06::try_part_2(input).unwrap(),
+            day06::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day07(input in day07_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day07::try_part_1(input).unwrap(),
+            day07::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day07::try_part_2(input).unwrap(),
+            day07::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day08((input, steps) in day08_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day08::try_part_1(input, steps).unwrap(),
+            day08::reference::part_1(input, steps)
+        );
+        prop_assert_eq!(
+            day08::try_part_2(input).unwrap(),
+            day08::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day09(input in day09_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day09::try_part_1(input).unwrap(),
+            day09::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day09::try_part_2(input).unwrap(),
+            day09::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day11(input in day11_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day11::try_part_1(input).unwrap(),
+            day11::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day11::try_part_2(input).unwrap(),
+            day11::reference::part_2(input)
+        );
+    }
+
+    #[test]
+    fn day12(input in day12_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day12::try_part_1(input).unwrap(),
+            day12::reference::part_1(input)
+        );
+    }
+}
+
+proptest! {
+    // Every machine in part 2 is a call into the solver.
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn day10(input in day10_input()) {
+        let input = input.as_bytes();
+        prop_assert_eq!(
+            day10::try_part_1(input).unwrap(),
+            day10::reference::part_1(input)
+        );
+        prop_assert_eq!(
+            day10::try_part_2(input).unwrap(),
+            day10::reference::part_2(input)
+        );
+    }
+}
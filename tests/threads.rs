@@ -0,0 +1,70 @@
+//! Spreading the work over threads must not change any answer, nor which
+//! error a broken input reports.
+
+use std::time::Duration;
+
+use aoc_2025::{
+    SOLUTIONS, Solution, r#gen,
+    threads::{self, Threads},
+};
+
+fn solve_all(solution: &dyn Solution, input: &[u8], threads: Threads) -> Vec<String> {
+    threads::run(threads, || {
+        solution
+            .parts()
+            .iter()
+            .map(|&part| match solution.solve(part, input) {
+                Some(Ok(answer)) => answer.to_string(),
+                Some(Err(e)) => e.to_string(),
+                None => String::new(),
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn parallel_matches_sequential() {
+    for &solution in SOLUTIONS {
+        for seed in 0..3 {
+            let input = r#gen::generate(solution.day(), seed, 30).unwrap();
+            let sequential = solve_all(solution, input.as_bytes(), Threads::Sequential);
+            for threads in [Threads::Global, Threads::Pool(4)] {
+                assert_eq!(
+                    solve_all(solution, input.as_bytes(), threads),
+                    sequential,
+                    "day {}, seed {seed}",
+                    solution.day()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn parse_errors_do_not_depend_on_threads() {
+    let input = b"11-22,95-1x5,998-1012,12-4a";
+    let solution = aoc_2025::solution(2).unwrap();
+    let sequential = solve_all(solution, input, Threads::Sequential);
+    assert!(sequential[0].contains("column 10"), "{}", sequential[0]);
+    for _ in 0..10 {
+        assert_eq!(solve_all(solution, input, Threads::Pool(4)), sequential);
+    }
+}
+
+#[test]
+fn parallel_reports_the_first_error() {
+    // Every sixteenth item fails, and the later items finish first.
+    let first_error = || {
+        threads::map(0..64u64, |i| {
+            std::thread::sleep(Duration::from_micros(10 * (64 - i)));
+            if i % 16 == 5 { Err(i) } else { Ok(i) }
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+    };
+    for threads in [Threads::Sequential, Threads::Global, Threads::Pool(4)] {
+        for _ in 0..10 {
+            assert_eq!(threads::run(threads, first_error), Err(5), "{threads:?}");
+        }
+    }
+}